@@ -0,0 +1,163 @@
+use std::fmt;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Result of a part, independent of the integer type the day works with.
+///
+/// Numbers compare by value, so `Signed(5) == Unsigned(5)`, and text compares
+/// against the display form of the other answer.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+    Unimplemented,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Unimplemented => write!(f, "unimplemented"),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Unimplemented, Answer::Unimplemented) => true,
+            (Answer::Unimplemented, _) | (_, Answer::Unimplemented) => false,
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Signed(a), Answer::Unsigned(b)) | (Answer::Unsigned(b), Answer::Signed(a)) => {
+                u64::try_from(*a) == Ok(*b)
+            },
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+macro_rules! from_integer {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self { Answer::$variant(value as $target) }
+            }
+        )*
+    };
+}
+
+from_integer!(Unsigned, u64, u8, u16, u32, u64, usize);
+from_integer!(Signed, i64, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self { Answer::Text(value) }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self { Answer::Text(value.to_string()) }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Signed(n) => serializer.serialize_i64(*n),
+            Answer::Unsigned(n) => serializer.serialize_u64(*n),
+            Answer::Text(s) => serializer.serialize_str(s),
+            Answer::Unimplemented => serializer.serialize_none(),
+        }
+    }
+}
+
+struct AnswerVisitor;
+
+impl<'de> Visitor<'de> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an integer, a string or null")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Answer, E> {
+        Ok(match u64::try_from(value) {
+            Ok(value) => Answer::Unsigned(value),
+            Err(_) => Answer::Signed(value),
+        })
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Answer, E> {
+        Ok(Answer::Unsigned(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Answer, E> { Ok(value.into()) }
+
+    fn visit_none<E: de::Error>(self) -> Result<Answer, E> { Ok(Answer::Unimplemented) }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Answer, E> { Ok(Answer::Unimplemented) }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Answer, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn equality_across_variants() {
+        assert_eq!(Answer::Signed(5), Answer::Unsigned(5));
+        assert_eq!(Answer::Unsigned(5), Answer::Signed(5));
+        assert_ne!(Answer::Signed(-5), Answer::Unsigned(5));
+        assert_ne!(Answer::Signed(-1), Answer::Unsigned(u64::MAX));
+        assert_eq!(Answer::from("35"), Answer::Unsigned(35));
+        assert_eq!(Answer::from("-35"), Answer::Signed(-35));
+        assert_ne!(Answer::from("035"), Answer::Unsigned(35));
+        assert_eq!(Answer::Unimplemented, Answer::Unimplemented);
+        assert_ne!(Answer::from("unimplemented"), Answer::Unimplemented);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let answers = vec![
+            Answer::Signed(-3),
+            Answer::Unsigned(u64::MAX),
+            Answer::from("ABC"),
+            Answer::Unimplemented,
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, r#"[-3,18446744073709551615,"ABC",null]"#);
+        assert_eq!(serde_json::from_str::<Vec<Answer>>(&json).unwrap(), answers);
+
+        // Non-negative integers always come back unsigned.
+        let answer: Answer = serde_json::from_str("7").unwrap();
+        assert!(matches!(answer, Answer::Unsigned(7)));
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        let answers = BTreeMap::from([
+            ("part1".to_string(), Answer::Signed(-3)),
+            ("part2".to_string(), Answer::from("ABC")),
+        ]);
+        let toml = toml::to_string(&answers).unwrap();
+        assert_eq!(toml, "part1 = -3\npart2 = \"ABC\"\n");
+        assert_eq!(
+            toml::from_str::<BTreeMap<String, Answer>>(&toml).unwrap(),
+            answers
+        );
+
+        let answer: BTreeMap<String, Answer> = toml::from_str("part1 = 42").unwrap();
+        assert!(matches!(answer["part1"], Answer::Unsigned(42)));
+    }
+}
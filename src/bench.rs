@@ -0,0 +1,263 @@
+use std::hint::black_box;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::runner;
+use crate::solution::{Part, Puzzle};
+
+/// Summary of the timings of a step, in nanoseconds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_by(f64::total_cmp);
+
+        let count = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / count;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+        let middle = nanos.len() / 2;
+        let median = if nanos.len().is_multiple_of(2) {
+            (nanos[middle - 1] + nanos[middle]) / 2.0
+        } else {
+            nanos[middle]
+        };
+
+        Stats {
+            min: nanos[0],
+            median,
+            mean,
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Failure {
+    Parse(ParseError),
+    /// A step panicked or gave no answer, with the reason.
+    Failed {
+        step: String,
+        message: String,
+    },
+}
+
+impl From<ParseError> for Failure {
+    fn from(value: ParseError) -> Self { Failure::Parse(value) }
+}
+
+/// Call `f`, turning a panic into a failure of `step`.
+fn catch<R>(step: &str, f: impl FnOnce() -> R) -> Result<R, Failure> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| Failure::Failed {
+        step: step.to_string(),
+        message: runner::panic_message(&*payload),
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Measurement {
+    /// `parse`, `part1` or `part2`.
+    pub step: String,
+    pub stats: Stats,
+}
+
+/// Timings of a day, which can be saved and compared against later runs.
+#[derive(Serialize, Deserialize)]
+pub struct Baseline {
+    pub day: u8,
+    pub runs: u32,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Baseline {
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    pub fn get(&self, step: &str) -> Option<&Stats> {
+        self.measurements
+            .iter()
+            .find(|m| m.step == step)
+            .map(|m| &m.stats)
+    }
+}
+
+/// Parse `input` and solve `parts` `warmup + runs` times, keeping the timings
+/// of the last `runs` iterations. Stops at the first step that fails or
+/// panics.
+pub fn bench(
+    puzzle: &dyn Puzzle,
+    parts: &[Part],
+    input: &Input,
+    warmup: u32,
+    runs: u32,
+) -> Result<Baseline, Failure> {
+    let mut parse = Vec::new();
+    let mut solve = vec![Vec::new(); parts.len()];
+
+    for run in 0..warmup + runs {
+        let start = Instant::now();
+        let parsed = catch("parse", || puzzle.parse(input))??;
+        let elapsed = start.elapsed();
+
+        let timings = parts
+            .iter()
+            .map(|&part| {
+                let step = format!("part{}", part.number());
+                let start = Instant::now();
+                catch(&step, || black_box(puzzle.solve(part, &parsed)))?.map_err(|message| {
+                    Failure::Failed {
+                        step: step.clone(),
+                        message,
+                    }
+                })?;
+                Ok(start.elapsed())
+            })
+            .collect::<Result<Vec<Duration>, Failure>>()?;
+
+        if run >= warmup {
+            parse.push(elapsed);
+            for (samples, timing) in solve.iter_mut().zip(timings) {
+                samples.push(timing);
+            }
+        }
+    }
+
+    let mut measurements = vec![Measurement {
+        step: "parse".to_string(),
+        stats: Stats::new(&parse),
+    }];
+    for (part, samples) in parts.iter().zip(solve) {
+        measurements.push(Measurement {
+            step: format!("part{}", part.number()),
+            stats: Stats::new(&samples),
+        });
+    }
+
+    Ok(Baseline {
+        day: puzzle.metadata().day,
+        runs,
+        measurements,
+    })
+}
+
+fn duration(nanos: f64) -> String { format!("{:.2?}", Duration::from_nanos(nanos as u64)) }
+
+/// Print the timings of `current`, along with the change of the median
+/// against `baseline` when there is one.
+pub fn print_stats(current: &Baseline, baseline: Option<&Baseline>) {
+    print!(
+        "{:<6} {:>10} {:>10} {:>10} {:>10}",
+        "Step", "Min", "Median", "Mean", "Stddev"
+    );
+    if baseline.is_some() {
+        print!(" {:>10}", "Change");
+    }
+    println!();
+
+    for Measurement { step, stats } in &current.measurements {
+        print!(
+            "{:<6} {:>10} {:>10} {:>10} {:>10}",
+            step,
+            duration(stats.min),
+            duration(stats.median),
+            duration(stats.mean),
+            duration(stats.stddev)
+        );
+        if let Some(baseline) = baseline {
+            let change = match baseline.get(step) {
+                Some(previous) => {
+                    format!("{:+.1}%", (stats.median / previous.median - 1.0) * 100.0)
+                },
+                None => "-".to_string(),
+            };
+            print!(" {:>10}", change);
+        }
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{Metadata, Solution};
+
+    /// Solves its first part, and panics on the second one.
+    struct Fragile;
+
+    impl Solution for Fragile {
+        type Model = usize;
+        type Output = usize;
+
+        const METADATA: Metadata = Metadata {
+            day: 3,
+            title: "Fragile",
+        };
+
+        fn parse(input: &Input) -> Result<usize, ParseError> { Ok(input.text().len()) }
+
+        fn part1(input: &usize) -> usize { *input }
+
+        fn part2(_: &usize) -> usize { panic!("no way through") }
+    }
+
+    fn nanos(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn stats_of_odd_and_even_samples() {
+        let stats = Stats::new(&nanos(&[3, 1, 2]));
+        assert_eq!((stats.min, stats.median, stats.mean), (1.0, 2.0, 2.0));
+        assert!((stats.stddev - (2.0f64 / 3.0).sqrt()).abs() < 1e-9);
+
+        let stats = Stats::new(&nanos(&[4, 1, 3, 2]));
+        assert_eq!((stats.min, stats.median, stats.mean), (1.0, 2.5, 2.5));
+        assert!((stats.stddev - 1.25f64.sqrt()).abs() < 1e-9);
+
+        let stats = Stats::new(&nanos(&[7]));
+        assert_eq!(
+            (stats.min, stats.median, stats.mean, stats.stddev),
+            (7.0, 7.0, 7.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn panics_fail_their_step() {
+        let input = Input::from("abc");
+        let baseline = bench(&Fragile, &[Part::Part1], &input, 1, 2).unwrap();
+        let steps: Vec<&str> = baseline
+            .measurements
+            .iter()
+            .map(|m| m.step.as_str())
+            .collect();
+        assert_eq!(steps, ["parse", "part1"]);
+
+        match bench(&Fragile, &Part::ALL, &input, 1, 2) {
+            Err(Failure::Failed { step, message }) => {
+                assert_eq!(
+                    (step.as_str(), message.as_str()),
+                    ("part2", "no way through")
+                );
+            },
+            _ => panic!("expected part2 to fail"),
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::runner::{self, Outcome, Report, Task};
+
+/// Default location of the cache, relative to the working directory.
+pub const PATH: &str = ".cache/answers.json";
+
+/// An answer, along with everything it was computed from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// Hash of the input, from [`hash`].
+    pub input: String,
+    /// Version of the crate that computed the answer.
+    pub version: String,
+    pub answer: Answer,
+}
+
+/// Answers of earlier runs, stored as JSON.
+pub struct Cache {
+    path: PathBuf,
+    /// Version of the crate whose answers are reused.
+    version: String,
+    entries: Vec<Entry>,
+}
+
+/// FNV-1a hash of `bytes`, which unlike the hasher of the standard library is
+/// stable across Rust releases.
+fn fnv(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// Hash of the content of `input`.
+pub fn hash(input: &Input) -> String { fnv(input.text().as_bytes()) }
+
+/// Version of the running crate, which answers are cached for. Bump it when
+/// a solution changes its answers, or clear the cache.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+impl Cache {
+    /// Read the cache at `path`, which is empty if the file does not exist,
+    /// reusing the answers of the running [`VERSION`] only.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> { Self::load_for(path, VERSION) }
+
+    /// Like [`Cache::load`], reusing the answers computed by `version`.
+    pub fn load_for(path: impl Into<PathBuf>, version: &str) -> io::Result<Self> {
+        let path = path.into();
+        let entries = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Cache {
+            path,
+            version: version.to_string(),
+            entries,
+        })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.entries)?)
+    }
+
+    /// Remove the cache at `path`, returning whether there was one.
+    pub fn clear(path: &Path) -> io::Result<bool> {
+        match fs::remove_file(path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn entries(&self) -> &[Entry] { &self.entries }
+
+    pub fn get(&self, year: u16, day: u8, part: u8, input: &str) -> Option<&Answer> {
+        self.entries
+            .iter()
+            .find(|e| {
+                e.year == year
+                    && e.day == day
+                    && e.part == part
+                    && e.input == input
+                    && e.version == self.version
+            })
+            .map(|e| &e.answer)
+    }
+
+    pub fn insert(&mut self, year: u16, day: u8, part: u8, input: String, answer: Answer) {
+        self.entries
+            .retain(|e| !(e.year == year && e.day == day && e.part == part && e.input == input));
+        self.entries.push(Entry {
+            year,
+            day,
+            part,
+            input,
+            version: self.version.clone(),
+            answer,
+        });
+        self.entries.sort_by_key(|e| (e.year, e.day, e.part));
+    }
+
+    /// Like [`runner::run_tasks`], taking the answers from the cache when
+    /// possible and storing the new ones. Only the parts missing from the
+    /// cache are solved, still from a single parsing of their input.
+    pub fn run_tasks(
+        &mut self,
+        year: u16,
+        tasks: &[Task],
+        jobs: usize,
+        timeout: Option<Duration>,
+    ) -> Vec<Report> {
+        let hashes: Vec<Option<String>> = tasks
+            .iter()
+            .map(|task| Input::read(&task.input).ok().map(|input| hash(&input)))
+            .collect();
+
+        let mut reports: Vec<Vec<Report>> = Vec::new();
+        let mut pending: Vec<Task> = Vec::new();
+        for (task, hash) in tasks.iter().zip(&hashes) {
+            let day = task.puzzle.metadata().day;
+            let mut cached = Vec::new();
+            let mut parts = Vec::new();
+            for &part in &task.parts {
+                match hash
+                    .as_ref()
+                    .and_then(|h| self.get(year, day, part.number(), h))
+                {
+                    Some(answer) => cached.push(Report {
+                        outcome: Outcome::Solved(answer.clone()),
+                        input: Some(task.input.clone()),
+                        cached: true,
+                        ..Report::skipped(day, part, "")
+                    }),
+                    None => parts.push(part),
+                }
+            }
+            reports.push(cached);
+            pending.push(Task {
+                parts,
+                ..task.clone()
+            });
+        }
+
+        let mut solved = runner::run_tasks(&pending, jobs, timeout).into_iter();
+        for ((reports, task), hash) in reports.iter_mut().zip(&pending).zip(&hashes) {
+            let solved: Vec<Report> = solved.by_ref().take(task.parts.len()).collect();
+            for report in &solved {
+                if let (Outcome::Solved(answer), Some(hash)) = (&report.outcome, hash) {
+                    let part = report.part.number();
+                    self.insert(year, report.day, part, hash.clone(), answer.clone());
+                }
+            }
+            reports.extend(solved);
+            reports.sort_by_key(|r| r.part.number());
+        }
+
+        reports.into_iter().flatten().collect()
+    }
+}
+
+/// Solve `tasks` of `year` like [`runner::run_tasks`], going through the cache
+/// at [`PATH`] unless `no_cache` is set. A cache that cannot be read is
+/// reported on stderr and ignored, as is one that cannot be written.
+pub fn run_tasks(
+    year: u16,
+    tasks: &[Task],
+    jobs: usize,
+    timeout: Option<Duration>,
+    no_cache: bool,
+) -> Vec<Report> {
+    if no_cache {
+        return runner::run_tasks(tasks, jobs, timeout);
+    }
+
+    let mut cache = match Cache::load(PATH) {
+        Ok(cache) => cache,
+        Err(e) => {
+            eprintln!("could not read {PATH}, ignoring it: {e}");
+            return runner::run_tasks(tasks, jobs, timeout);
+        },
+    };
+    let reports = cache.run_tasks(year, tasks, jobs, timeout);
+    if let Err(e) = cache.save() {
+        eprintln!("could not write {PATH}: {e}");
+    }
+    reports
+}
+
+pub fn print_entries(entries: &[Entry]) {
+    let width = entries
+        .iter()
+        .map(|e| e.answer.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    println!(
+        "{:>4} {:>4} {:>4}  {:<width$}  {:<16}  Version",
+        "Year", "Day", "Part", "Answer", "Input"
+    );
+    for entry in entries {
+        println!(
+            "{:>4} {:>4} {:>4}  {:<width$}  {:<16}  {}",
+            entry.year,
+            entry.day,
+            entry.part,
+            entry.answer.to_string(),
+            entry.input,
+            entry.version
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::{Counted, Scratch};
+    use crate::solution::Part;
+
+    #[test]
+    fn answers_are_kept_per_input_and_version() {
+        let dir = Scratch::new("cache-entries");
+        let path = dir.join("answers.json");
+        let mut cache = Cache::load_for(&path, "0.1.0").unwrap();
+        cache.insert(2023, 1, 1, "abc".into(), 3u32.into());
+        cache.insert(2023, 1, 1, "abc".into(), 4u32.into());
+
+        assert_eq!(cache.get(2023, 1, 1, "abc"), Some(&4u32.into()));
+        assert_eq!(cache.entries().len(), 1);
+        assert_eq!(cache.get(2023, 1, 1, "abd"), None);
+        assert_eq!(cache.get(2023, 1, 2, "abc"), None);
+        assert_eq!(cache.get(2022, 1, 1, "abc"), None);
+
+        cache.save().unwrap();
+        let same = Cache::load_for(&path, "0.1.0").unwrap();
+        assert_eq!(same.get(2023, 1, 1, "abc"), Some(&4u32.into()));
+        let mut other = Cache::load_for(&path, "0.2.0").unwrap();
+        assert_eq!(other.get(2023, 1, 1, "abc"), None);
+
+        other.insert(2023, 1, 1, "abc".into(), 5u32.into());
+        assert_eq!(other.get(2023, 1, 1, "abc"), Some(&5u32.into()));
+        assert_eq!(other.entries().len(), 1);
+    }
+
+    #[test]
+    fn tasks_only_solve_the_missing_parts() {
+        let dir = Scratch::new("cache-tasks");
+        let input = dir.join("day1.txt");
+        fs::write(&input, "abc\n").unwrap();
+        let mut cache = Cache::load_for(dir.join("answers.json"), VERSION).unwrap();
+        let task = |parts: &[Part]| Task {
+            puzzle: &Counted,
+            parts: parts.to_vec(),
+            input: input.clone(),
+        };
+        let solve = |cache: &mut Cache, task: Task| {
+            let reports = cache.run_tasks(2023, &[task], 1, None);
+            reports
+                .into_iter()
+                .map(|r| (r.part, r.outcome, r.cached))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            solve(&mut cache, task(&[Part::Part1])),
+            [(Part::Part1, Outcome::Solved(3u32.into()), false)]
+        );
+        assert_eq!(
+            solve(&mut cache, task(&Part::ALL)),
+            [
+                (Part::Part1, Outcome::Solved(3u32.into()), true),
+                (Part::Part2, Outcome::Solved(6u32.into()), false),
+            ]
+        );
+        assert_eq!(Counted::parses(&input), 2);
+
+        assert_eq!(
+            solve(&mut cache, task(&Part::ALL)),
+            [
+                (Part::Part1, Outcome::Solved(3u32.into()), true),
+                (Part::Part2, Outcome::Solved(6u32.into()), true),
+            ]
+        );
+        assert_eq!(Counted::parses(&input), 2);
+
+        fs::write(&input, "abcd\n").unwrap();
+        assert_eq!(
+            solve(&mut cache, task(&[Part::Part2])),
+            [(Part::Part2, Outcome::Solved(8u32.into()), false)]
+        );
+        assert_eq!(Counted::parses(&input), 3);
+    }
+}
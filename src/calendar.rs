@@ -0,0 +1,131 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::days;
+use crate::input::{self, Input};
+use crate::solution::Puzzle;
+
+/// Puzzles of one event year.
+pub struct Calendar {
+    pub year: u16,
+    /// Every day of the event, in order.
+    pub days: &'static [&'static dyn Puzzle],
+    /// Module holding the days, relative to `src`.
+    pub module: &'static str,
+}
+
+impl Calendar {
+    /// Number of days in the event.
+    pub fn length(&self) -> u8 { self.days.len() as u8 }
+
+    /// Parse a day of the event, such as `day5` or `5`.
+    pub fn parse_day(&self, value: &str) -> Result<u8, String> {
+        let number = value.strip_prefix("day").unwrap_or(value);
+        match number.parse() {
+            Ok(day) if (1..=self.length()).contains(&day) => Ok(day),
+            _ => Err(format!("expected one of day1..=day{}", self.length())),
+        }
+    }
+
+    pub fn get(&self, day: u8) -> Option<&'static dyn Puzzle> {
+        self.days.iter().copied().find(|p| p.metadata().day == day)
+    }
+
+    /// Directory holding the inputs of the event, named `dayN.txt`.
+    pub fn inputs(&self) -> PathBuf { PathBuf::from(format!("inputs/{}", self.year)) }
+
+    /// Conventional location of the input of `day`.
+    pub fn input(&self, day: u8) -> PathBuf { self.inputs().join(format!("day{day}.txt")) }
+
+    /// Puzzle of `day`, as long as it is implemented.
+    pub fn solved(&self, day: u8) -> Result<&'static dyn Puzzle, String> {
+        let puzzle = self
+            .get(day)
+            .ok_or_else(|| format!("day{day} is not part of {}", self.year))?;
+        if !puzzle.implemented() {
+            let metadata = puzzle.metadata();
+            return Err(format!(
+                "day{} ({}) is not implemented yet",
+                metadata.day, metadata.title
+            ));
+        }
+        Ok(puzzle)
+    }
+
+    /// Implemented puzzle of `day` along with its input, read from `path` or
+    /// else from [`Calendar::input`].
+    pub fn open(
+        &self,
+        day: u8,
+        path: Option<&Path>,
+    ) -> Result<(&'static dyn Puzzle, Input), String> {
+        let puzzle = self.solved(day)?;
+        let path = path.map_or_else(|| self.input(day), Path::to_path_buf);
+        match Input::read(&path) {
+            Ok(input) => Ok((puzzle, input)),
+            Err(e) => Err(format!("could not read {}: {e}", input::name(&path))),
+        }
+    }
+}
+
+/// Every supported event, from the oldest to the latest.
+pub static YEARS: [Calendar; 1] = [Calendar {
+    year: 2023,
+    days: &days::DAYS,
+    module: "days",
+}];
+
+pub fn get(year: u16) -> Option<&'static Calendar> { YEARS.iter().find(|c| c.year == year) }
+
+pub fn latest() -> &'static Calendar { YEARS.last().unwrap() }
+
+/// Split the event off command line `args`, whose first argument after the
+/// program name can be the year of the event, defaulting to the latest one.
+pub fn select(mut args: Vec<OsString>) -> Result<(&'static Calendar, Vec<OsString>), String> {
+    let year = args
+        .get(1)
+        .and_then(|arg| arg.to_str())
+        .filter(|arg| arg.len() == 4 && arg.bytes().all(|b| b.is_ascii_digit()));
+    let Some(year) = year else {
+        return Ok((latest(), args));
+    };
+
+    let year: u16 = year.parse().unwrap();
+    let calendar = get(year).ok_or_else(|| format!("no solutions for year {year}"))?;
+    args.remove(1);
+    Ok((calendar, args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<OsString> { args.iter().map(OsString::from).collect() }
+
+    #[test]
+    fn the_year_is_taken_off_the_arguments() {
+        let (calendar, rest) = select(args(&["advent", "2023", "day5", "part2"])).unwrap();
+        assert_eq!(calendar.year, 2023);
+        assert_eq!(rest, args(&["advent", "day5", "part2"]));
+
+        let (calendar, rest) = select(args(&["advent", "day5", "part2"])).unwrap();
+        assert_eq!(calendar.year, latest().year);
+        assert_eq!(rest, args(&["advent", "day5", "part2"]));
+        assert!(select(args(&["advent"])).is_ok());
+
+        let error = select(args(&["advent", "2022", "day1", "part1"])).err();
+        assert_eq!(error.unwrap(), "no solutions for year 2022");
+    }
+
+    #[test]
+    fn days_are_checked_against_the_event() {
+        let calendar = latest();
+        assert_eq!(calendar.parse_day("day25"), Ok(25));
+        assert_eq!(calendar.parse_day("7"), Ok(7));
+        assert_eq!(
+            calendar.parse_day("day26"),
+            Err("expected one of day1..=day25".to_string())
+        );
+        assert!(calendar.parse_day("day0").is_err());
+    }
+}
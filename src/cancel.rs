@@ -0,0 +1,62 @@
+//! Cooperative cancellation of long running solvers.
+//!
+//! A solver cannot be stopped from the outside, so long loops call
+//! [`checkpoint`] on each round instead. Once the [`Token`] attached to their
+//! thread is cancelled, the next checkpoint unwinds out of the solver.
+
+use std::cell::RefCell;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Payload of the unwinding started by [`checkpoint`].
+struct Cancelled;
+
+thread_local! {
+    /// Token attached to this thread, if any.
+    static TOKEN: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+/// Shared flag asking the solver of a thread to stop.
+#[derive(Clone, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    /// Have the checkpoints of the current thread stop once `self` is
+    /// cancelled.
+    pub fn attach(self) { TOKEN.with(|token| *token.borrow_mut() = Some(self)); }
+
+    pub fn cancel(&self) { self.0.store(true, Ordering::Relaxed); }
+
+    pub fn is_cancelled(&self) -> bool { self.0.load(Ordering::Relaxed) }
+}
+
+/// Stop the current solver if the token attached to its thread was cancelled.
+pub fn checkpoint() {
+    let cancelled = TOKEN.with(|token| token.borrow().as_ref().is_some_and(Token::is_cancelled));
+    if cancelled {
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn checkpoints_stop_once_cancelled() {
+        let token = Token::default();
+        let attached = token.clone();
+        let stopped = thread::spawn(move || {
+            attached.attach();
+            checkpoint();
+            token.cancel();
+            panic::catch_unwind(checkpoint).is_err()
+        });
+        assert!(stopped.join().unwrap());
+
+        checkpoint();
+    }
+}
@@ -0,0 +1,366 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fmt, fs, io, thread};
+
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::solution::Part;
+
+/// Website of the event.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the session token, which takes precedence
+/// over the one of the config file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Default location of the config file, relative to the working directory.
+pub const CONFIG: &str = "advent.toml";
+
+/// File remembering when the last request was sent, shared by every run.
+pub const LAST_REQUEST: &str = ".cache/last-request";
+
+/// Time waited between two requests unless configured otherwise.
+pub const INTERVAL: Duration = Duration::from_secs(5);
+
+/// Settings of the client, all of them optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Value of the `session` cookie of a logged in browser.
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+    /// Minimum number of seconds between two requests.
+    pub interval: Option<f64>,
+}
+
+impl Config {
+    /// Read the config at `path`, which is empty if the file does not exist.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The session token from the environment, or else from the config.
+    pub fn session(&self) -> Option<String> {
+        std::env::var(SESSION_VAR)
+            .ok()
+            .or_else(|| self.session.clone())
+            .filter(|s| !s.trim().is_empty())
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The server answered with an unexpected status, explained by the first
+    /// line of its body.
+    Status(u16, String),
+    /// The server asked to slow down, possibly saying for how long.
+    RateLimited(Option<Duration>),
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Status(status, body) if body.is_empty() => write!(f, "server answered {status}"),
+            Error::Status(status, body) => write!(f, "server answered {status}: {}", body.trim()),
+            Error::RateLimited(Some(wait)) => {
+                write!(f, "rate limited, retry in {}s", wait.as_secs())
+            },
+            Error::RateLimited(None) => write!(f, "rate limited, retry later"),
+            Error::Transport(message) => write!(f, "{message}"),
+            Error::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self { Error::Io(value) }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(value: ureq::Error) -> Self {
+        match value {
+            ureq::Error::Status(429, response) => Error::RateLimited(
+                response
+                    .header("Retry-After")
+                    .and_then(|s| s.trim().parse().ok())
+                    .map(Duration::from_secs),
+            ),
+            ureq::Error::Status(status, response) => {
+                let body = response.into_string().unwrap_or_default();
+                Error::Status(status, body.lines().next().unwrap_or_default().to_string())
+            },
+            ureq::Error::Transport(transport) => Error::Transport(transport.to_string()),
+        }
+    }
+}
+
+/// Keeps requests at least `interval` apart, across runs, by storing the time
+/// of the last one in a file.
+pub struct Throttle {
+    interval: Duration,
+    state: PathBuf,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration, state: impl Into<PathBuf>) -> Self {
+        Throttle {
+            interval,
+            state: state.into(),
+        }
+    }
+
+    fn now() -> Duration {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+
+    /// Sleep until `interval` has passed since the last request, then record
+    /// a new one.
+    pub fn wait(&self) -> io::Result<()> {
+        let last = fs::read_to_string(&self.state)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .map(Duration::from_millis);
+        if let Some(last) = last {
+            let next = last + self.interval;
+            if let Some(remaining) = next.checked_sub(Self::now()) {
+                thread::sleep(remaining.min(self.interval));
+            }
+        }
+
+        if let Some(parent) = self.state.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Round up, so that the interval is never cut short.
+        let millis = Self::now().as_nanos().div_ceil(1_000_000);
+        fs::write(&self.state, millis.to_string())
+    }
+}
+
+/// What [`Client::fetch`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded,
+    /// The input was already there, so nothing was requested.
+    Existing,
+}
+
+/// How the server judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The answer was not judged, as the last one was given too recently.
+    TooRecent,
+    /// The part was already solved, or is not unlocked yet.
+    WrongLevel,
+    /// The page did not match any known message.
+    Unknown,
+}
+
+impl Verdict {
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "wrong, too high",
+            Verdict::TooLow => "wrong, too low",
+            Verdict::Wrong => "wrong",
+            Verdict::TooRecent => "not judged, answered too recently",
+            Verdict::WrongLevel => "not judged, already solved or still locked",
+            Verdict::Unknown => "unknown",
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// Reply of the server to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Submission {
+    pub verdict: Verdict,
+    /// Time to wait before submitting again.
+    pub wait: Option<Duration>,
+}
+
+/// Parse a duration such as `1m 5s` or `37s`.
+fn parse_wait(text: &str) -> Option<Duration> {
+    text.split_whitespace()
+        .try_fold(Duration::ZERO, |total, word| {
+            let (number, scale) = if let Some(number) = word.strip_suffix('h') {
+                (number, 3600)
+            } else if let Some(number) = word.strip_suffix('m') {
+                (number, 60)
+            } else {
+                (word.strip_suffix('s')?, 1)
+            };
+            Some(total + Duration::from_secs(number.parse::<u64>().ok()? * scale))
+        })
+}
+
+impl Submission {
+    /// Read the verdict and cooldown out of the page answering a submission.
+    pub fn read(page: &str) -> Self {
+        let verdict = if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if page.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::TooRecent
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        };
+
+        let wait = if let Some((_, rest)) = page.split_once("You have ") {
+            rest.split_once(" left to wait")
+                .and_then(|(wait, _)| parse_wait(wait))
+        } else if page.contains("please wait one minute") {
+            Some(Duration::from_secs(60))
+        } else if let Some((_, rest)) = page.split_once("please wait ") {
+            rest.split_once(" minutes")
+                .and_then(|(minutes, _)| minutes.parse().ok())
+                .map(|minutes: u64| Duration::from_secs(minutes * 60))
+        } else {
+            None
+        };
+
+        Submission { verdict, wait }
+    }
+}
+
+/// Client of the website of the event, authenticated by a session token.
+pub struct Client {
+    base_url: String,
+    session: String,
+    throttle: Throttle,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, throttle: Throttle) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            throttle,
+            agent,
+        }
+    }
+
+    /// Build a client from the config at `path`, explaining what is missing
+    /// when it cannot.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config =
+            Config::load(path).map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        Client::from_config(&config).ok_or_else(|| {
+            format!(
+                "no session token, set {SESSION_VAR} or `session` in {}",
+                path.display()
+            )
+        })
+    }
+
+    /// Build a client from `config`, which must provide a session token,
+    /// falling back to the defaults for everything else.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let interval = config
+            .interval
+            .and_then(|s| Duration::try_from_secs_f64(s).ok())
+            .unwrap_or(INTERVAL);
+
+        Some(Client::new(
+            config.base_url.as_deref().unwrap_or(BASE_URL),
+            &config.session()?,
+            Throttle::new(interval, LAST_REQUEST),
+        ))
+    }
+
+    fn url(&self, year: u16, day: u8, page: &str) -> String {
+        format!("{}/{year}/day/{day}{page}", self.base_url)
+    }
+
+    fn cookie(&self) -> String { format!("session={}", self.session) }
+
+    /// Download the input of `day` to `path`, unless the file already exists.
+    pub fn fetch(&self, year: u16, day: u8, path: &Path) -> Result<Fetched, Error> {
+        if path.exists() {
+            return Ok(Fetched::Existing);
+        }
+
+        self.throttle.wait()?;
+        let input = self
+            .agent
+            .get(&self.url(year, day, "/input"))
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Write to a temporary file first so that an interrupted download
+        // never leaves a truncated input behind.
+        let partial = path.with_extension("part");
+        fs::write(&partial, input)?;
+        fs::rename(&partial, path)?;
+
+        Ok(Fetched::Downloaded)
+    }
+
+    /// Submit `answer` to `part` of `day`.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+    ) -> Result<Submission, Error> {
+        self.throttle.wait()?;
+        let page = self
+            .agent
+            .post(&self.url(year, day, "/answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[
+                ("level", &part.number().to_string()),
+                ("answer", &answer.to_string()),
+            ])?
+            .into_string()?;
+
+        Ok(Submission::read(&page))
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::cancel::checkpoint;
+
+/// Shape of the sequence `x, f(x), f(f(x)), ...` of a function over a finite
+/// set of states: `tail` states before the first repeated one, then a cycle of
+/// `length` states.
+///
+/// The detectors below never return for a sequence that does not repeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub tail: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Smallest step reaching the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.tail {
+            n
+        } else {
+            self.tail + (n - self.tail) % self.length
+        }
+    }
+
+    /// State reached after `n` steps from `start`, following at most
+    /// `tail + length` of them.
+    pub fn nth<T, F>(&self, start: &T, mut next: F, n: usize) -> T
+    where
+        T: Clone,
+        F: FnMut(&T) -> T,
+    {
+        (0..self.reduce(n)).fold(start.clone(), |state, _| next(&state))
+    }
+}
+
+/// Cycle found by [`hashed`], along with every state up to its end.
+#[derive(Debug, Clone)]
+pub struct Found<T> {
+    pub cycle: Cycle,
+    /// The first `tail + length` states, starting from the first one.
+    pub states: Vec<T>,
+}
+
+impl<T> Found<T> {
+    /// State reached after `n` steps.
+    pub fn nth(&self, n: usize) -> &T { &self.states[self.cycle.reduce(n)] }
+}
+
+/// Find the cycle by remembering the step of every state, which takes a
+/// single pass but keeps all of them in memory.
+pub fn hashed<T, F>(start: T, mut next: F) -> Found<T>
+where
+    T: Clone + Hash + Eq,
+    F: FnMut(&T) -> T,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+
+    loop {
+        checkpoint();
+        if let Some(&tail) = seen.get(&state) {
+            let length = states.len() - tail;
+            return Found {
+                cycle: Cycle { tail, length },
+                states,
+            };
+        }
+        seen.insert(state.clone(), states.len());
+        let following = next(&state);
+        states.push(state);
+        state = following;
+    }
+}
+
+/// Find the length of the cycle once inside it, then the tail by walking
+/// two pointers that far apart from `start` until they meet.
+fn tail<T, F>(start: &T, next: &mut F, length: usize) -> Cycle
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> T,
+{
+    let mut slow = start.clone();
+    let mut fast = (0..length).fold(start.clone(), |state, _| next(&state));
+    let mut tail = 0;
+    while slow != fast {
+        checkpoint();
+        slow = next(&slow);
+        fast = next(&fast);
+        tail += 1;
+    }
+
+    Cycle { tail, length }
+}
+
+/// Floyd's tortoise and hare, in constant memory.
+pub fn floyd<T, F>(start: &T, mut next: F) -> Cycle
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> T,
+{
+    let mut slow = next(start);
+    let mut fast = next(&slow);
+    while slow != fast {
+        checkpoint();
+        slow = next(&slow);
+        fast = next(&fast);
+        fast = next(&fast);
+    }
+
+    let mut length = 1;
+    let mut lap = next(&slow);
+    while lap != slow {
+        checkpoint();
+        lap = next(&lap);
+        length += 1;
+    }
+
+    tail(start, &mut next, length)
+}
+
+/// Brent's algorithm, in constant memory and with fewer steps than
+/// [`floyd`].
+pub fn brent<T, F>(start: &T, mut next: F) -> Cycle
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> T,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut slow = start.clone();
+    let mut fast = next(start);
+    while slow != fast {
+        checkpoint();
+        if power == length {
+            slow = fast.clone();
+            power *= 2;
+            length = 0;
+        }
+        fast = next(&fast);
+        length += 1;
+    }
+
+    tail(start, &mut next, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Visits 0, 1, 2, 3, 4, 5, 6 then back to 3.
+    fn next(x: &u32) -> u32 {
+        if *x == 6 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn variants_agree() {
+        let expected = Cycle { tail: 3, length: 4 };
+        assert_eq!(hashed(0, next).cycle, expected);
+        assert_eq!(floyd(&0, next), expected);
+        assert_eq!(brent(&0, next), expected);
+        assert_eq!(brent(&3, next), Cycle { tail: 0, length: 4 });
+    }
+
+    #[test]
+    fn nth_state() {
+        let found = hashed(0, next);
+        assert_eq!(*found.nth(2), 2);
+        assert_eq!(*found.nth(7), 3);
+        assert_eq!(*found.nth(1_000_000_001), 5);
+        assert_eq!(found.cycle.nth(&0, next, 1_000_000_001), 5);
+    }
+}
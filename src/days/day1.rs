@@ -1,36 +1,96 @@
-pub fn solution1(input: Vec<String>) -> u32 {
-    let mut output: Vec<u32> = Vec::new();
-
-    for line in input {
-        let chars: Vec<char> = line.chars().filter(|d| d.is_numeric()).collect();
-        let first = chars.first().unwrap();
-        let last = if let Some(last) = chars.last() { last } else { first };
-        let calibration: u32 = format!("{}{}", first, last).parse().unwrap(); // chars.last()
-        output.push(calibration);
-    }
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
+/// A digit of a line, which may be spelled out.
+#[derive(Debug, Clone, Copy)]
+pub struct Digit {
+    pub value: u32,
+    pub spelled: bool,
+}
 
-    output.iter().sum()
+/// Number made of the first and last of `digits`, if there are any.
+fn calibration(mut digits: impl DoubleEndedIterator<Item = u32>) -> Option<u32> {
+    let first = digits.next()?;
+    let last = digits.next_back().unwrap_or(first);
+    Some(first * 10 + last)
 }
 
-fn replace_numbers(input: String) -> String {
+/// Sum of the calibration values of the lines. Spelled out digits do not
+/// count, so a line without any other digit adds nothing.
+pub fn solution1(input: &[Vec<Digit>]) -> u32 {
     input
-        .replace("one", "o1e")
-        .replace("two", "t2o")
-        .replace("three", "t3e")
-        .replace("four", "4")
-        .replace("five", "5e")
-        .replace("six", "6")
-        .replace("seven", "7n")
-        .replace("eight", "e8t")
-        .replace("nine", "n9e")
+        .iter()
+        .filter_map(|line| calibration(line.iter().filter(|d| !d.spelled).map(|d| d.value)))
+        .sum()
 }
 
-pub fn solution2(input: Vec<String>) -> u32 {
-    let mut parsed = Vec::new();
+/// Sum of the calibration values of the lines, spelled out digits included.
+pub fn solution2(input: &[Vec<Digit>]) -> u32 {
+    input
+        .iter()
+        .filter_map(|line| calibration(line.iter().map(|d| d.value)))
+        .sum()
+}
+
+const SPELLED: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Digits of `line` in order. Spelled out digits may overlap, as in
+/// `eightwo`, which holds both 8 and 2.
+fn parse(line: &str) -> Result<Vec<Digit>, ParseError> {
+    let digits: Vec<Digit> = line
+        .char_indices()
+        .filter_map(|(n, c)| match c.to_digit(10) {
+            Some(value) => Some(Digit {
+                value,
+                spelled: false,
+            }),
+            None => SPELLED
+                .iter()
+                .position(|spelled| line[n..].starts_with(spelled))
+                .map(|position| Digit {
+                    value: position as u32 + 1,
+                    spelled: true,
+                }),
+        })
+        .collect();
 
-    for line in input {
-        parsed.push(replace_numbers(line));
+    if digits.is_empty() {
+        Err(ParseError::new(1, 1, line, "expected a digit"))
+    } else {
+        Ok(digits)
     }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Model = Vec<Vec<Digit>>;
+    type Output = u32;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: include_str!("examples/day1.txt"),
+            part1: Some(Answer::Unsigned(142)),
+            part2: None,
+        },
+        Example {
+            input: include_str!("examples/day1b.txt"),
+            part1: None,
+            part2: Some(Answer::Unsigned(281)),
+        },
+    ];
+    const METADATA: Metadata = Metadata {
+        day: 1,
+        title: "Trebuchet?!",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse_lines(input.lines(), parse) }
+
+    fn part1(input: &Self::Model) -> u32 { solution1(input) }
 
-    solution1(parsed)
+    fn part2(input: &Self::Model) -> u32 { solution2(input) }
 }
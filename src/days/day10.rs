@@ -1,15 +1,50 @@
 use std::cmp::max;
 use std::collections::VecDeque;
 
-#[derive(Copy, Clone)]
-struct Node {
+use crate::answer::Answer;
+use crate::cancel::checkpoint;
+use crate::error::ParseError;
+use crate::geometry::{Coord, Direction};
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
+#[derive(Debug, Copy, Clone)]
+pub struct Node {
     pub left: bool,
     pub right: bool,
     pub top: bool,
     pub bottom: bool,
 }
 
+/// Sides of a node, in the order the pipes are followed.
+const SIDES: [Direction; 4] = [
+    Direction::Left,
+    Direction::Right,
+    Direction::Up,
+    Direction::Down,
+];
+
 impl Node {
+    /// Whether the pipe goes out towards `direction`.
+    pub fn connects(self, direction: Direction) -> bool {
+        match direction {
+            Direction::Up => self.top,
+            Direction::Right => self.right,
+            Direction::Down => self.bottom,
+            Direction::Left => self.left,
+        }
+    }
+
+    fn side_mut(&mut self, direction: Direction) -> &mut bool {
+        match direction {
+            Direction::Up => &mut self.top,
+            Direction::Right => &mut self.right,
+            Direction::Down => &mut self.bottom,
+            Direction::Left => &mut self.left,
+        }
+    }
+
     pub fn type_line(self) -> Option<Vertical> {
         if self.top || self.left || self.right || self.bottom {
             match (self.top, self.bottom) {
@@ -24,9 +59,11 @@ impl Node {
     }
 }
 
-impl From<char> for Node {
-    fn from(value: char) -> Self {
-        match value {
+impl TryFrom<char> for Node {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, char> {
+        Ok(match value {
             '.' => Node {
                 left: false,
                 right: false,
@@ -75,185 +112,107 @@ impl From<char> for Node {
                 top: true,
                 bottom: true,
             },
-            _ => unreachable!(),
-        }
+            _ => return Err(value),
+        })
     }
 }
 
 #[derive(PartialEq)]
-enum Vertical {
+pub enum Vertical {
     Up,
     Down,
     Both,
     Not,
 }
 
-fn parse(input: String) -> (Vec<Node>, Option<usize>) {
-    let mut start = None;
-    let nodes = input
-        .chars()
-        .enumerate()
-        .map(|(n, i)| {
-            if i == 'S' {
-                start = Some(n)
-            };
-            i.into()
-        })
-        .collect();
-    (nodes, start)
+#[derive(Debug, Clone)]
+pub struct Maze {
+    pub grid: Grid<Node>,
+    pub start: Coord,
 }
 
-pub fn solution1(input: Vec<String>) -> usize {
-    let mut grid = Vec::new();
-    let mut start = (usize::MAX, usize::MAX);
-
-    for (n, line) in input.iter().enumerate() {
-        let (l, s) = parse(line.to_owned());
-
-        grid.push(l);
-
-        if let Some(s) = s {
-            start = (n, s);
-        }
-    }
+fn parse(input: &str) -> Result<Maze, ParseError> {
+    let mut grid: Grid<Node> = Grid::parse(input)?;
+    let start = input
+        .lines()
+        .enumerate()
+        .find_map(|(n, line)| line.find('S').map(|s| Coord::new(n, s)))
+        .ok_or_else(|| {
+            let first = input.lines().next().unwrap_or_default();
+            ParseError::new(1, 1, first, "no starting position `S`")
+        })?;
 
     // Remove broken pipes
-    for (i, l) in grid.clone().iter().cloned().enumerate() {
-        for (j, n) in l.iter().enumerate() {
-            // println!("{i} {j}");
-            if j + 1 == l.len() || (n.right && !l[j + 1].left) {
-                grid[i][j].right = false;
-            }
-            if j == 0 || (n.left && !l[j - 1].right) {
-                grid[i][j].left = false;
-            }
-            if i == 0 || (n.top && !grid[i - 1][j].bottom) {
-                grid[i][j].top = false;
-            }
-            if i + 1 == grid.len() || (n.bottom && !grid[i + 1][j].top) {
-                grid[i][j].bottom = false;
-            }
+    let pipes = grid.clone();
+    for (position, _) in pipes.iter() {
+        for direction in SIDES {
+            let connected = pipes
+                .step(position, direction)
+                .is_some_and(|neighbour| pipes[neighbour].connects(direction.reverse()));
+            *grid[position].side_mut(direction) &= connected;
         }
     }
 
-    let mut queue: VecDeque<((usize, usize), usize)> = [(start, 0)].into();
+    Ok(Maze { grid, start })
+}
+
+pub fn solution1(input: &Maze) -> usize {
+    let Maze { mut grid, start } = input.clone();
+
+    let mut queue: VecDeque<(Coord, usize)> = [(start, 0)].into();
     let mut max_dist = 0;
 
     // Explore pipes
     while !queue.is_empty() {
+        checkpoint();
         let (node, distance) = queue.pop_front().unwrap();
-        let Node {
-            left,
-            right,
-            top,
-            bottom,
-        } = grid[node.0][node.1];
-        if left {
-            grid[node.0][node.1 - 1].right = false;
-            max_dist = max(max_dist, distance + 1);
-            queue.push_back(((node.0, node.1 - 1), distance + 1))
-        }
-        if right {
-            grid[node.0][node.1 + 1].left = false;
-            max_dist = max(max_dist, distance + 1);
-            queue.push_back(((node.0, node.1 + 1), distance + 1))
-        }
-        if top {
-            grid[node.0 - 1][node.1].bottom = false;
-            max_dist = max(max_dist, distance + 1);
-            queue.push_back(((node.0 - 1, node.1), distance + 1))
-        }
-        if bottom {
-            grid[node.0 + 1][node.1].top = false;
+        let current = grid[node];
+        for direction in SIDES.into_iter().filter(|&d| current.connects(d)) {
+            let Some(next) = grid.step(node, direction) else {
+                continue;
+            };
+            *grid[next].side_mut(direction.reverse()) = false;
             max_dist = max(max_dist, distance + 1);
-            queue.push_back(((node.0 + 1, node.1), distance + 1))
+            queue.push_back((next, distance + 1))
         }
     }
 
     max_dist
 }
 
-pub fn solution2(input: Vec<String>) -> usize {
-    let mut grid = Vec::new();
-    let mut start = (usize::MAX, usize::MAX);
-
-    for (n, line) in input.iter().enumerate() {
-        let (l, s) = parse(line.to_owned());
-
-        grid.push(l);
+pub fn solution2(input: &Maze) -> usize {
+    let Maze { mut grid, start } = input.clone();
 
-        if let Some(s) = s {
-            start = (n, s);
-        }
-    }
-
-    // Remove broken pipes
-    for (i, l) in grid.clone().iter().cloned().enumerate() {
-        for (j, n) in l.iter().enumerate() {
-            if j + 1 == l.len() || (n.right && !l[j + 1].left) {
-                grid[i][j].right = false;
-            }
-            if j == 0 || (n.left && !l[j - 1].right) {
-                grid[i][j].left = false;
-            }
-            if i == 0 || (n.top && !grid[i - 1][j].bottom) {
-                grid[i][j].top = false;
-            }
-            if i + 1 == grid.len() || (n.bottom && !grid[i + 1][j].top) {
-                grid[i][j].bottom = false;
-            }
-        }
-    }
+    let mut nest_grid = Grid::new(
+        grid.width(),
+        grid.height(),
+        Node {
+            left: false,
+            right: false,
+            top: false,
+            bottom: false,
+        },
+    );
 
-    let mut nest_grid = vec![
-        vec![
-            Node {
-                left: false,
-                right: false,
-                top: false,
-                bottom: false
-            };
-            grid[0].len()
-        ];
-        grid.len()
-    ];
-
-    nest_grid[start.0][start.1] = grid[start.0][start.1];
-    let mut queue: VecDeque<((usize, usize), usize)> = [(start, 0)].into();
+    nest_grid[start] = input.grid[start];
+    let mut queue: VecDeque<(Coord, usize)> = [(start, 0)].into();
     let mut last = (start, 1);
 
     // Filter out nodes that are not in the loop
     while !queue.is_empty() {
+        checkpoint();
         let (node, distance) = queue.pop_front().unwrap();
         if (node, distance) != last {
             last = (node, distance);
 
-            let Node {
-                left,
-                right,
-                top,
-                bottom,
-            } = grid[node.0][node.1];
-
-            if left {
-                nest_grid[node.0][node.1 - 1] = grid[node.0][node.1 - 1];
-                grid[node.0][node.1 - 1].right = false;
-                queue.push_back(((node.0, node.1 - 1), distance + 1))
-            }
-            if right {
-                nest_grid[node.0][node.1 + 1] = grid[node.0][node.1 + 1];
-                grid[node.0][node.1 + 1].left = false;
-                queue.push_back(((node.0, node.1 + 1), distance + 1))
-            }
-            if top {
-                nest_grid[node.0 - 1][node.1] = grid[node.0 - 1][node.1];
-                grid[node.0 - 1][node.1].bottom = false;
-                queue.push_back(((node.0 - 1, node.1), distance + 1))
-            }
-            if bottom {
-                nest_grid[node.0 + 1][node.1] = grid[node.0 + 1][node.1];
-                grid[node.0 + 1][node.1].top = false;
-                queue.push_back(((node.0 + 1, node.1), distance + 1))
+            let current = grid[node];
+            for direction in SIDES.into_iter().filter(|&d| current.connects(d)) {
+                let Some(next) = grid.step(node, direction) else {
+                    continue;
+                };
+                nest_grid[next] = input.grid[next];
+                *grid[next].side_mut(direction.reverse()) = false;
+                queue.push_back((next, distance + 1))
             }
         }
     }
@@ -261,7 +220,7 @@ pub fn solution2(input: Vec<String>) -> usize {
     let mut inside = 0;
 
     // Ray casting algorithm
-    for line in nest_grid {
+    for line in nest_grid.rows() {
         let mut last_vertical = None;
         let mut outside = true;
         for n in line {
@@ -299,3 +258,48 @@ pub fn solution2(input: Vec<String>) -> usize {
 
     inside
 }
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Model = Maze;
+    type Output = usize;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: include_str!("examples/day10.txt"),
+            part1: Some(Answer::Unsigned(8)),
+            part2: None,
+        },
+        Example {
+            input: include_str!("examples/day10b.txt"),
+            part1: None,
+            part2: Some(Answer::Unsigned(10)),
+        },
+        Example {
+            input: include_str!("examples/day10c.txt"),
+            part1: None,
+            part2: Some(Answer::Unsigned(4)),
+        },
+        Example {
+            input: include_str!("examples/day10d.txt"),
+            part1: None,
+            part2: Some(Answer::Unsigned(4)),
+        },
+        Example {
+            input: include_str!("examples/day10e.txt"),
+            part1: None,
+            part2: Some(Answer::Unsigned(8)),
+        },
+    ];
+    const METADATA: Metadata = Metadata {
+        day: 10,
+        title: "Pipe Maze",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse(input.text()) }
+
+    fn part1(input: &Self::Model) -> usize { solution1(input) }
+
+    fn part2(input: &Self::Model) -> usize { solution2(input) }
+}
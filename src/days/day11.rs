@@ -2,8 +2,15 @@ use std::cmp::{max, min};
 
 use itertools::Itertools;
 
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::geometry::Coord;
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum Space {
+pub enum Space {
     Void(usize),
     Galaxy,
 }
@@ -17,49 +24,40 @@ impl Space {
     }
 }
 
-impl From<char> for Space {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Space {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, char> {
         match value {
-            '.' => Space::Void(1),
-            '#' => Space::Galaxy,
-            _ => unreachable!(),
+            '.' => Ok(Space::Void(1)),
+            '#' => Ok(Space::Galaxy),
+            _ => Err(value),
         }
     }
 }
 
-fn expand(input: Vec<String>, distance: usize) -> usize {
-    let mut space: Vec<Vec<Space>> = Vec::new();
-
-    for line in input {
-        let l = line.chars().map(Space::from).collect();
-        space.push(l);
-    }
-
-    let h = space[0].len();
+fn expand(input: &Grid<Space>, distance: usize) -> usize {
+    let mut space = input.clone();
 
-    for (i, l) in space.clone().iter().cloned().enumerate().rev() {
-        if l.iter().all(|&x| x.is_void()) {
-            space[i] = vec![Space::Void(distance); h];
+    for i in 0..space.height() {
+        if space.row(i).iter().all(|&x| x.is_void()) {
+            space.row_mut(i).fill(Space::Void(distance));
         }
     }
 
-    for j in (0..h).rev() {
-        if space.iter().all(|l| l[j].is_void()) {
-            for void in space.iter_mut() {
-                void[j] = Space::Void(distance);
-            }
+    for j in 0..space.width() {
+        if space.column(j).all(|&x| x.is_void()) {
+            space
+                .column_mut(j)
+                .for_each(|void| *void = Space::Void(distance));
         }
     }
 
-    let mut galaxies = Vec::new();
-
-    for (i, l) in space.iter().enumerate() {
-        for (j, &s) in l.iter().enumerate() {
-            if s == Space::Galaxy {
-                galaxies.push((i, j));
-            }
-        }
-    }
+    let galaxies: Vec<Coord> = space
+        .iter()
+        .filter(|&(_, &s)| s == Space::Galaxy)
+        .map(|(position, _)| position)
+        .collect();
 
     galaxies
         .iter()
@@ -68,20 +66,21 @@ fn expand(input: Vec<String>, distance: usize) -> usize {
             let first = pair[0];
             let second = pair[1];
             let mut dist = 0;
-            for line in space
-                .iter()
-                .take(max(first.0, second.0))
-                .skip(min(first.0, second.0))
+            for s in space
+                .column(first.column)
+                .take(max(first.row, second.row))
+                .skip(min(first.row, second.row))
             {
-                match line[first.1] {
+                match s {
                     Space::Galaxy => dist += 1,
                     Space::Void(d) => dist += d,
                 }
             }
-            for s in space[first.0]
+            for s in space
+                .row(first.row)
                 .iter()
-                .take(max(first.1, second.1))
-                .skip(min(first.1, second.1))
+                .take(max(first.column, second.column))
+                .skip(min(first.column, second.column))
             {
                 match s {
                     Space::Galaxy => dist += 1,
@@ -93,6 +92,29 @@ fn expand(input: Vec<String>, distance: usize) -> usize {
         .sum()
 }
 
-pub fn solution1(input: Vec<String>) -> usize { expand(input, 2) }
+pub fn solution1(input: &Grid<Space>) -> usize { expand(input, 2) }
+
+pub fn solution2(input: &Grid<Space>) -> usize { expand(input, 1000000) }
+
+pub struct Day11;
 
-pub fn solution2(input: Vec<String>) -> usize { expand(input, 1000000) }
+impl Solution for Day11 {
+    type Model = Grid<Space>;
+    type Output = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day11.txt"),
+        part1: Some(Answer::Unsigned(374)),
+        part2: Some(Answer::Unsigned(82000210)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 11,
+        title: "Cosmic Expansion",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { Grid::parse(input.text()) }
+
+    fn part1(input: &Self::Model) -> usize { solution1(input) }
+
+    fn part2(input: &Self::Model) -> usize { solution2(input) }
+}
@@ -1,12 +1,18 @@
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::cancel::checkpoint;
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day12.pest"]
 pub struct SpringParser;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum Spring {
+pub enum Spring {
     Operational,
     Damaged,
     Unknown,
@@ -23,9 +29,10 @@ impl From<char> for Spring {
     }
 }
 
-fn parse(input: String) -> (Vec<Spring>, Vec<usize>) {
-    let pairs = SpringParser::parse(Rule::springs, &input)
-        .unwrap()
+pub type Row = (Vec<Spring>, Vec<usize>);
+
+fn parse(input: &str) -> Result<Row, ParseError> {
+    let pairs = SpringParser::parse(Rule::springs, input)?
         .next()
         .unwrap()
         .into_inner();
@@ -34,16 +41,16 @@ fn parse(input: String) -> (Vec<Spring>, Vec<usize>) {
     for pair in pairs {
         match pair.as_rule() {
             Rule::spring => springs.extend(pair.as_str().chars().map(Spring::from)),
-            Rule::number => counts.push(pair.as_str().parse().unwrap()),
+            Rule::number => counts.push(parse_number(&pair)?),
             Rule::EOI => (),
             _ => unreachable!(),
         };
     }
 
-    (springs, counts)
+    Ok((springs, counts))
 }
 
-fn search_matches((mut row, counts): (Vec<Spring>, Vec<usize>)) -> usize {
+pub fn search_matches((mut row, counts): Row) -> usize {
     // Extend beginning in case it not starts with operational
     if row.first().unwrap() != &Spring::Operational {
         let mut row_extended = vec![Spring::Operational];
@@ -60,6 +67,7 @@ fn search_matches((mut row, counts): (Vec<Spring>, Vec<usize>)) -> usize {
     total_possible.append(&mut vec![0; row.len() - first]);
 
     for count in counts {
+        checkpoint();
         let mut next_total = vec![0; row.len() + 1];
         let mut group = 0;
         for (n, &s) in row.iter().enumerate() {
@@ -88,22 +96,41 @@ fn search_matches((mut row, counts): (Vec<Spring>, Vec<usize>)) -> usize {
     total_possible.last().unwrap().to_owned()
 }
 
-pub fn solution1(input: Vec<String>) -> usize {
-    input
-        .iter()
-        .map(|l| search_matches(parse(l.to_owned())))
-        .sum()
-}
+pub fn solution1(input: &[Row]) -> usize { input.iter().cloned().map(search_matches).sum() }
 
-pub fn solution2(input: Vec<String>) -> usize {
+pub fn solution2(input: &[Row]) -> usize {
     input
         .iter()
-        .map(|l| {
-            let (row, counts) = l.split_once(' ').unwrap();
-            let row = (0..5).map(|_| row).collect::<Vec<_>>();
-            let counts = (0..5).map(|_| counts).collect::<Vec<_>>();
-            let l = row.join("?") + " " + &counts.join(",");
-            search_matches(parse(l))
+        .map(|(row, counts)| {
+            let mut unfolded = row.clone();
+            for _ in 1..5 {
+                unfolded.push(Spring::Unknown);
+                unfolded.extend(row);
+            }
+            search_matches((unfolded, counts.repeat(5)))
         })
         .sum()
 }
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Model = Vec<Row>;
+    type Output = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day12.txt"),
+        part1: Some(Answer::Unsigned(21)),
+        part2: Some(Answer::Unsigned(525152)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 12,
+        title: "Hot Springs",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse_lines(input.lines(), parse) }
+
+    fn part1(input: &Self::Model) -> usize { solution1(input) }
+
+    fn part2(input: &Self::Model) -> usize { solution2(input) }
+}
@@ -1,19 +1,26 @@
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day13.pest"]
 pub struct LavaParser;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum Lava {
+pub enum Lava {
     Ash,
     Rock,
 }
 
-fn parse(input: String) -> Vec<Vec<Vec<Lava>>> {
-    let pairs = LavaParser::parse(Rule::lava, &input)
-        .unwrap()
+pub type Pattern = Grid<Lava>;
+
+fn parse(input: &str) -> Result<Vec<Pattern>, ParseError> {
+    let pairs = LavaParser::parse(Rule::lava, input)?
         .next()
         .unwrap()
         .into_inner();
@@ -21,6 +28,8 @@ fn parse(input: String) -> Vec<Vec<Vec<Lava>>> {
     for pair in pairs {
         match pair.as_rule() {
             Rule::pattern => {
+                let (first, column) = pair.line_col();
+                let text = pair.as_str();
                 let pairs = pair.into_inner();
                 let mut lines = Vec::new();
                 for pair in pairs {
@@ -35,38 +44,28 @@ fn parse(input: String) -> Vec<Vec<Vec<Lava>>> {
                     }
                     lines.push(line);
                 }
-                patterns.push(lines);
+                patterns.push(Grid::from_rows(lines).map_err(|n| {
+                    let row = text.lines().nth(n).unwrap_or_default();
+                    ParseError::new(first + n, column, row, "rows of different lengths")
+                })?);
             },
             Rule::EOI => (),
             _ => unreachable!(),
         };
     }
 
-    patterns
-}
-
-fn transpose<T>(mut v: Vec<Vec<T>>) -> Vec<Vec<T>> {
-    for inner in &mut v {
-        inner.reverse();
-    }
-    (0..v[0].len())
-        .map(|_| {
-            v.iter_mut()
-                .map(|inner| inner.pop().unwrap())
-                .collect::<Vec<T>>()
-        })
-        .collect()
+    Ok(patterns)
 }
 
-fn find_mirror(pattern: Vec<Vec<Lava>>, smudge: bool) -> usize {
+fn find_mirror(pattern: &Pattern, smudge: bool) -> usize {
     let mut horizontal = 0;
-    'outer: for i in 0..(pattern.len() - 1) {
+    'outer: for i in 0..(pattern.height() - 1) {
         let mut allow = smudge;
         let mut a = i;
         let mut b = i + 1;
         loop {
-            for j in 0..pattern[0].len() {
-                if pattern[a][j] != pattern[b][j] {
+            for (x, y) in pattern.row(a).iter().zip(pattern.row(b)) {
+                if x != y {
                     if !allow {
                         continue 'outer;
                     } else {
@@ -74,7 +73,7 @@ fn find_mirror(pattern: Vec<Vec<Lava>>, smudge: bool) -> usize {
                     }
                 }
             }
-            if a == 0 || b == pattern.len() - 1 {
+            if a == 0 || b == pattern.height() - 1 {
                 break;
             }
             a -= 1;
@@ -88,38 +87,53 @@ fn find_mirror(pattern: Vec<Vec<Lava>>, smudge: bool) -> usize {
     horizontal
 }
 
-pub fn solution1(input: Vec<String>) -> usize {
-    let patterns = parse(input.join("\n"));
-    patterns
+pub fn solution1(input: &[Pattern]) -> usize {
+    input
         .iter()
         .map(|p| {
-            let horizontal = find_mirror(p.to_vec(), false);
+            let horizontal = find_mirror(p, false);
             if horizontal != 0 {
                 100 * horizontal
             } else {
-                let p = transpose(p.to_vec());
-                find_mirror(p.to_vec(), false)
+                find_mirror(&p.transpose(), false)
             }
         })
         .sum()
 }
 
-pub fn solution2(input: Vec<String>) -> usize {
-    let patterns = parse(input.join("\n"));
-    patterns
+pub fn solution2(input: &[Pattern]) -> usize {
+    input
         .iter()
         .map(|p| {
-            let horizontal = find_mirror(p.to_vec(), true);
+            let horizontal = find_mirror(p, true);
             if horizontal != 0 {
                 100 * horizontal
             } else {
-                let p = transpose(p.to_vec());
-                find_mirror(p.to_vec(), true)
+                find_mirror(&p.transpose(), true)
             }
         })
-        .map(|x| {
-            println!("{x}");
-            x
-        })
         .sum()
 }
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Model = Vec<Pattern>;
+    type Output = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day13.txt"),
+        part1: Some(Answer::Unsigned(405)),
+        part2: Some(Answer::Unsigned(400)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 13,
+        title: "Point of Incidence",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse(input.text()) }
+
+    fn part1(input: &Self::Model) -> usize { solution1(input) }
+
+    fn part2(input: &Self::Model) -> usize { solution2(input) }
+}
@@ -1,129 +1,94 @@
-use std::collections::HashMap;
+use crate::answer::Answer;
+use crate::cycle;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
 
 #[derive(Hash, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-enum Platform {
+pub enum Platform {
     Space,
     Round,
     Cube,
 }
 
-fn split_vec(vec: &Vec<Platform>) -> Vec<&[Platform]> {
-    let mut result = Vec::new();
-    let mut start = 0;
+impl TryFrom<char> for Platform {
+    type Error = char;
 
-    for (i, item) in vec.iter().enumerate() {
-        if item == &Platform::Cube {
-            if start <= i {
-                result.push(&vec[start..i]);
-                result.push(&[Platform::Cube]);
-            }
-            start = i + 1;
+    fn try_from(value: char) -> Result<Self, char> {
+        match value {
+            '.' => Ok(Platform::Space),
+            'O' => Ok(Platform::Round),
+            '#' => Ok(Platform::Cube),
+            _ => Err(value),
         }
     }
+}
 
-    if start < vec.len() {
-        result.push(&vec[start..]);
+/// Roll the round rocks of every row to the start, or to the end if `right`.
+fn tilt(platform: &mut Grid<Platform>, right: bool) {
+    for line in platform.rows_mut() {
+        for rocks in line.split_mut(|&p| p == Platform::Cube) {
+            rocks.sort();
+            if !right {
+                rocks.reverse();
+            }
+        }
     }
-    result
 }
 
-fn transpose(mut v: Vec<Vec<Platform>>) -> Vec<Vec<Platform>> {
-    for inner in &mut v {
-        inner.reverse();
-    }
+fn parse(input: &str) -> Result<Grid<Platform>, ParseError> { Ok(Grid::parse(input)?.transpose()) }
 
-    (0..v[0].len())
-        .map(|_| v.iter_mut().map(|inner| inner.pop().unwrap()).collect())
-        .collect()
-}
+fn count(platform: &Grid<Platform>) -> usize {
+    let platform = platform.transpose();
 
-fn tilt(platform: Vec<Vec<Platform>>, right: bool) -> Vec<Vec<Platform>> {
-    let mut tilted = Vec::new();
-
-    for line in platform {
-        let splits = split_vec(&line);
-        tilted.push(
-            splits
-                .iter()
-                .flat_map(|&s| {
-                    let mut s = s.to_vec();
-                    s.sort();
-                    if right {
-                        s.to_vec()
-                    } else {
-                        s.reverse();
-                        s.to_vec()
-                    }
-                })
-                .collect(),
-        );
+    let mut count = 0;
+    for (n, line) in platform.rows().enumerate() {
+        count += (platform.height() - n) * line.iter().filter(|&&b| b == Platform::Round).count()
     }
-
-    tilted
+    count
 }
 
-fn parse(input: Vec<String>) -> Vec<Vec<Platform>> {
-    let mut output = Vec::new();
-
-    for line in input {
-        let mut l = Vec::new();
-        for c in line.chars() {
-            match c {
-                '.' => l.push(Platform::Space),
-                'O' => l.push(Platform::Round),
-                '#' => l.push(Platform::Cube),
-                _ => unreachable!(),
-            }
-        }
-        output.push(l);
-    }
+pub fn solution1(input: &Grid<Platform>) -> usize {
+    let mut platform = input.clone();
+    tilt(&mut platform, false);
 
-    transpose(output)
+    count(&platform)
 }
 
-fn count(platform: Vec<Vec<Platform>>) -> usize {
-    let platform = transpose(platform);
-
-    let mut count = 0;
-    for (n, line) in platform.iter().enumerate() {
-        count += (platform.len() - n)
-            * line
-                .iter()
-                .fold(0, |a, &b| a + if b == Platform::Round { 1 } else { 0 })
+/// Tilt the platform north, west, south then east.
+fn spin(platform: &Grid<Platform>) -> Grid<Platform> {
+    let mut platform = platform.clone();
+    for j in 0..4 {
+        tilt(&mut platform, j >> 1 == 1);
+        platform = platform.transpose();
     }
-    count
+    platform
 }
 
-pub fn solution1(input: Vec<String>) -> usize {
-    let mut platform = parse(input);
-    platform = tilt(platform, false);
-
-    count(platform)
+pub fn solution2(input: &Grid<Platform>) -> usize {
+    count(cycle::hashed(input.clone(), spin).nth(1000000000))
 }
 
-pub fn solution2(input: Vec<String>) -> usize {
-    let mut platform = parse(input);
+pub struct Day14;
 
-    let mut platmap: HashMap<Vec<Vec<Platform>>, usize> = HashMap::new();
-    let mut idxmap: HashMap<usize, usize> = HashMap::new();
+impl Solution for Day14 {
+    type Model = Grid<Platform>;
+    type Output = usize;
 
-    for i in 0..1000000000 {
-        for j in 0..4 {
-            platform = tilt(platform, j >> 1 == 1);
-            platform = transpose(platform);
-        }
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day14.txt"),
+        part1: Some(Answer::Unsigned(136)),
+        part2: Some(Answer::Unsigned(64)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 14,
+        title: "Parabolic Reflector Dish",
+    };
 
-        // Check if it's repeating cycle :D
-        if let Some(&n) = platmap.get(&platform) {
-            return idxmap
-                .get(&(n + (1000000000 - i - 1) % (i - n)))
-                .unwrap()
-                .to_owned();
-        }
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse(input.text()) }
 
-        let count = count(platform.clone());
-        platmap.insert(platform.clone(), i);
-        idxmap.insert(i, count);
-    }
-    count(platform)
+    fn part1(input: &Self::Model) -> usize { solution1(input) }
+
+    fn part2(input: &Self::Model) -> usize { solution2(input) }
 }
@@ -1,5 +1,10 @@
 use std::hash::{Hash, Hasher};
 
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 struct HolidayHasher {
     state: u8,
 }
@@ -11,8 +16,7 @@ impl HolidayHasher {
 impl Hasher for HolidayHasher {
     fn write(&mut self, bytes: &[u8]) {
         for &byte in bytes {
-            self.state += byte;
-            self.state *= 17;
+            self.state = self.state.wrapping_add(byte).wrapping_mul(17);
         }
     }
 
@@ -25,9 +29,9 @@ impl Hash for Step<'_> {
     fn hash<H: Hasher>(&self, state: &mut H) { state.write(self.0); }
 }
 
-pub fn solution1(input: Vec<String>) -> u64 {
-    input[0]
-        .split(',')
+pub fn solution1(input: &Sequence) -> u64 {
+    input
+        .steps()
         .map(|s| {
             let x = Step(s.as_bytes());
             let mut hasher = HolidayHasher::new();
@@ -37,13 +41,13 @@ pub fn solution1(input: Vec<String>) -> u64 {
         .sum()
 }
 
-pub fn solution2(input: Vec<String>) -> u64 {
+pub fn solution2(input: &Sequence) -> u64 {
     let mut hashmap: Vec<Vec<(&str, u64)>> = vec![Vec::new(); 256];
-    for s in input[0].split(',') {
+    for s in input.steps() {
         let (s, step, n) = if let Some((s, n)) = s.split_once('=') {
             (s, Step(s.as_bytes()), n.parse().ok())
         } else {
-            let s = s.strip_suffix('-').unwrap();
+            let s = s.strip_suffix('-').unwrap_or(s);
             (s, Step(s.as_bytes()), None)
         };
 
@@ -76,3 +80,68 @@ pub fn solution2(input: Vec<String>) -> u64 {
         })
         .sum()
 }
+
+/// Initialization sequence, kept as the line of comma separated steps it was
+/// read from.
+pub struct Sequence(String);
+
+impl Sequence {
+    /// Steps of the sequence, which either set a focal length with `=` or
+    /// remove a lens with `-`.
+    pub fn steps(&self) -> std::str::Split<'_, char> { self.0.split(',') }
+}
+
+/// Check the steps of `line`, which either set a focal length with `=` or
+/// remove a lens with `-`.
+fn parse(line: &str) -> Result<Sequence, ParseError> {
+    let mut column = 1;
+    for step in line.split(',') {
+        let valid = match step.split_once('=') {
+            Some((label, focal)) => !label.is_empty() && focal.parse::<u64>().is_ok(),
+            None => step.len() > 1 && step.ends_with('-'),
+        };
+        if !valid {
+            return Err(ParseError::new(
+                1,
+                column,
+                line,
+                format!("invalid step `{step}`"),
+            ));
+        }
+        column += step.chars().count() + 1;
+    }
+    Ok(Sequence(line.to_string()))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Model = Sequence;
+    type Output = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day15.txt"),
+        part1: Some(Answer::Unsigned(1320)),
+        part2: Some(Answer::Unsigned(145)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 15,
+        title: "Lens Library",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> {
+        match input.lines().next() {
+            Some(line) => parse(line),
+            None => Err(ParseError::new(
+                1,
+                1,
+                "",
+                "expected an initialization sequence",
+            )),
+        }
+    }
+
+    fn part1(input: &Self::Model) -> u64 { solution1(input) }
+
+    fn part2(input: &Self::Model) -> u64 { solution2(input) }
+}
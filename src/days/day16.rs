@@ -1,5 +1,13 @@
+use crate::answer::Answer;
+use crate::cancel::checkpoint;
+use crate::error::ParseError;
+use crate::geometry::{Coord, Direction};
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(Debug, Clone, Copy)]
-enum Tile {
+pub enum Tile {
     Positive,
     Negative,
     Horizontal,
@@ -7,215 +15,138 @@ enum Tile {
     Empty,
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
-        match value {
-            '.' => Tile::Empty,
-            '|' => Tile::Vertical,
-            '-' => Tile::Horizontal,
-            '/' => Tile::Negative,
-            '\\' => Tile::Positive,
-            _ => unreachable!(),
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Sign {
-    Positive,
-    Negative,
-}
+impl TryFrom<char> for Tile {
+    type Error = char;
 
-impl std::ops::Not for Sign {
-    type Output = Self;
-
-    fn not(self) -> Self::Output {
-        match self {
-            Sign::Positive => Sign::Negative,
-            Sign::Negative => Sign::Positive,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-
-enum Direction {
-    Horizontal,
-    Vertical,
-}
-
-impl std::ops::Not for Direction {
-    type Output = Self;
-
-    fn not(self) -> Self::Output {
-        match self {
-            Direction::Horizontal => Direction::Vertical,
-            Direction::Vertical => Direction::Horizontal,
+    fn try_from(value: char) -> Result<Self, char> {
+        match value {
+            '.' => Ok(Tile::Empty),
+            '|' => Ok(Tile::Vertical),
+            '-' => Ok(Tile::Horizontal),
+            '/' => Ok(Tile::Negative),
+            '\\' => Ok(Tile::Positive),
+            _ => Err(value),
         }
     }
 }
 
-struct Beam(Direction, Sign);
-
-impl Beam {
-    fn next(self, x: usize, y: usize, v: usize, h: usize) -> Option<(usize, usize, Self)> {
-        match self {
-            Beam(Direction::Vertical, Sign::Negative) => {
-                if x > 0 {
-                    return Some((x - 1, y, self));
-                }
+impl Tile {
+    /// Directions of the beams leaving the tile when entered going towards
+    /// `direction`.
+    fn deflect(self, direction: Direction) -> Vec<Direction> {
+        match (self, direction) {
+            (Tile::Positive, Direction::Up | Direction::Down)
+            | (Tile::Negative, Direction::Left | Direction::Right) => vec![direction.turn_left()],
+            (Tile::Positive, _) | (Tile::Negative, _) => vec![direction.turn_right()],
+            (Tile::Horizontal, _) if direction.is_vertical() => {
+                vec![Direction::Left, Direction::Right]
             },
-            Beam(Direction::Vertical, Sign::Positive) => {
-                if x + 1 < v {
-                    return Some((x + 1, y, self));
-                }
-            },
-            Beam(Direction::Horizontal, Sign::Negative) => {
-                if y > 0 {
-                    return Some((x, y - 1, self));
-                }
-            },
-            Beam(Direction::Horizontal, Sign::Positive) => {
-                if y + 1 < h {
-                    return Some((x, y + 1, self));
-                }
+            (Tile::Vertical, _) if !direction.is_vertical() => {
+                vec![Direction::Up, Direction::Down]
             },
+            _ => vec![direction],
         }
-        None
     }
 }
 
 #[derive(Debug, Clone, Copy)]
 struct Energized {
     energy: bool,
-    left: bool,
-    right: bool,
-    up: bool,
-    down: bool,
+    /// Directions of the beams that already went through.
+    beams: [bool; 4],
 }
 
 impl Energized {
     fn new() -> Self {
         Self {
             energy: false,
-            left: false,
-            right: false,
-            up: false,
-            down: false,
+            beams: [false; 4],
         }
     }
 
-    fn overlap(&mut self, beam: &Beam) -> bool {
-        match &beam {
-            Beam(Direction::Vertical, Sign::Negative) => {
-                if !self.energy || !self.left {
-                    self.left = true;
-                    self.energy = true;
-                    return false;
-                }
-            },
-            Beam(Direction::Vertical, Sign::Positive) => {
-                if !self.energy || !self.right {
-                    self.energy = true;
-                    self.right = true;
-                    return false;
-                }
-            },
-            Beam(Direction::Horizontal, Sign::Negative) => {
-                if !self.energy || !self.up {
-                    self.energy = true;
-                    self.up = true;
-                    return false;
-                }
-            },
-            Beam(Direction::Horizontal, Sign::Positive) => {
-                if !self.energy || !self.down {
-                    self.energy = true;
-                    self.down = true;
-                    return false;
-                }
-            },
+    fn overlap(&mut self, direction: Direction) -> bool {
+        let seen = &mut self.beams[direction as usize];
+        if *seen {
+            return true;
         }
-        true
+        *seen = true;
+        self.energy = true;
+        false
     }
 }
 
-fn search(grid: &Vec<Vec<Tile>>, mut queue: Vec<(usize, usize, Beam)>) -> usize {
-    let h = grid[0].len();
-    let v = grid.len();
-
-    let mut energized = vec![vec![Energized::new(); h]; v];
-    while let Some((x, y, beam)) = queue.pop() {
-        if energized[x][y].overlap(&beam) {
+fn search(grid: &Grid<Tile>, mut queue: Vec<(Coord, Direction)>) -> usize {
+    let mut energized = Grid::new(grid.width(), grid.height(), Energized::new());
+    while let Some((coord, direction)) = queue.pop() {
+        checkpoint();
+        if energized[coord].overlap(direction) {
             continue;
         }
         queue.extend(
-            match (grid[x][y], beam) {
-                (Tile::Positive, Beam(d, s)) => vec![Beam(!d, s).next(x, y, v, h)],
-                (Tile::Negative, Beam(d, s)) => vec![Beam(!d, !s).next(x, y, v, h)],
-                (Tile::Horizontal, Beam(Direction::Vertical, s)) => vec![
-                    Beam(Direction::Horizontal, s).next(x, y, v, h),
-                    Beam(Direction::Horizontal, !s).next(x, y, v, h),
-                ],
-                (Tile::Vertical, Beam(Direction::Horizontal, s)) => vec![
-                    Beam(Direction::Vertical, s).next(x, y, v, h),
-                    Beam(Direction::Vertical, !s).next(x, y, v, h),
-                ],
-                (_, b) => vec![b.next(x, y, v, h)],
-            }
-            .into_iter()
-            .flatten(),
+            grid[coord]
+                .deflect(direction)
+                .into_iter()
+                .filter_map(|d| Some((grid.step(coord, d)?, d))),
         );
     }
 
-    energized
-        .iter()
-        .map(|l| l.iter().map(|&x| x.energy as usize).sum::<usize>())
-        .sum()
+    energized.iter().filter(|(_, e)| e.energy).count()
 }
 
-pub fn solution1(input: Vec<String>) -> usize {
-    let grid = input
-        .iter()
-        .map(|l| l.chars().map(|i| i.into()).collect())
-        .collect();
-    search(
-        &grid,
-        [(0, 0, Beam(Direction::Horizontal, Sign::Positive))].into(),
-    )
+const EMPTY: &str = "the contraption is empty";
+
+pub fn solution1(input: &Grid<Tile>) -> Result<usize, String> {
+    if input.width() == 0 || input.height() == 0 {
+        return Err(EMPTY.to_string());
+    }
+    Ok(search(input, [(Coord::new(0, 0), Direction::Right)].into()))
 }
 
-pub fn solution2(input: Vec<String>) -> usize {
-    let grid: Vec<Vec<_>> = input
-        .iter()
-        .map(|l| l.chars().map(|i| i.into()).collect())
-        .collect();
+pub fn solution2(input: &Grid<Tile>) -> Result<usize, String> {
+    let grid = input;
+    if grid.width() == 0 || grid.height() == 0 {
+        return Err(EMPTY.to_string());
+    }
 
-    let v = grid.len();
-    let h = grid[0].len();
+    let v = grid.height();
+    let h = grid.width();
     let mut energies = Vec::new();
 
     for x in 0..v {
+        energies.push(search(grid, [(Coord::new(x, 0), Direction::Right)].into()));
         energies.push(search(
-            &grid,
-            [(x, 0, Beam(Direction::Horizontal, Sign::Positive))].into(),
-        ));
-        energies.push(search(
-            &grid,
-            [(x, h - 1, Beam(Direction::Horizontal, Sign::Negative))].into(),
+            grid,
+            [(Coord::new(x, h - 1), Direction::Left)].into(),
         ));
     }
 
     for y in 0..h {
-        energies.push(search(
-            &grid,
-            [(0, y, Beam(Direction::Vertical, Sign::Positive))].into(),
-        ));
-        energies.push(search(
-            &grid,
-            [(v - 1, y, Beam(Direction::Vertical, Sign::Negative))].into(),
-        ));
+        energies.push(search(grid, [(Coord::new(0, y), Direction::Down)].into()));
+        energies.push(search(grid, [(Coord::new(v - 1, y), Direction::Up)].into()));
     }
 
-    energies.iter().max().unwrap().to_owned()
+    energies.into_iter().max().ok_or_else(|| EMPTY.to_string())
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Model = Grid<Tile>;
+    type Output = Result<usize, String>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day16.txt"),
+        part1: Some(Answer::Unsigned(46)),
+        part2: Some(Answer::Unsigned(51)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 16,
+        title: "The Floor Will Be Lava",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { Grid::parse(input.text()) }
+
+    fn part1(input: &Self::Model) -> Result<usize, String> { solution1(input) }
+
+    fn part2(input: &Self::Model) -> Result<usize, String> { solution2(input) }
 }
@@ -1,57 +1,13 @@
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
 
-#[derive(Hash, Debug, Copy, Clone, Eq, PartialEq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl std::ops::Not for Direction {
-    type Output = Direction;
-
-    fn not(self) -> Self::Output {
-        match self {
-            Direction::Up => Direction::Down,
-            Direction::Down => Direction::Up,
-            Direction::Left => Direction::Right,
-            Direction::Right => Direction::Left,
-        }
-    }
-}
-
-#[derive(Hash, Debug, Copy, Clone, Eq, PartialEq)]
-struct Coord(usize, usize);
-
-impl Coord {
-    fn next(self, direction: Direction, v: usize, h: usize) -> Option<Coord> {
-        match direction {
-            Direction::Up => {
-                if self.0 > 0 {
-                    return Some(Coord(self.0 - 1, self.1));
-                }
-            },
-            Direction::Down => {
-                if self.0 + 1 < v {
-                    return Some(Coord(self.0 + 1, self.1));
-                }
-            },
-            Direction::Left => {
-                if self.1 > 0 {
-                    return Some(Coord(self.0, self.1 - 1));
-                }
-            },
-            Direction::Right => {
-                if self.1 + 1 < h {
-                    return Some(Coord(self.0, self.1 + 1));
-                }
-            },
-        }
-        None
-    }
-}
+use crate::answer::Answer;
+use crate::cancel::checkpoint;
+use crate::error::ParseError;
+use crate::geometry::{Coord, Direction};
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 struct Node {
@@ -62,10 +18,8 @@ struct Node {
 }
 
 impl Node {
-    fn next(self, grid: &Vec<Vec<usize>>, ultra: bool) -> Vec<Self> {
+    fn next(self, grid: &Grid<usize>, ultra: bool) -> Vec<Self> {
         let mut next = Vec::new();
-        let v = grid.len();
-        let h = grid[0].len();
         for direction in [
             Direction::Down,
             Direction::Right,
@@ -80,8 +34,8 @@ impl Node {
             {
                 continue;
             }
-            if let Some(coord) = self.coord.next(direction, v, h) {
-                let cost = self.cost + grid[coord.0][coord.1];
+            if let Some(coord) = grid.step(self.coord, direction) {
+                let cost = self.cost + grid[coord];
                 let repeat = if self.last == direction { self.repeat + 1 } else { 1 };
                 next.push(Node {
                     coord,
@@ -103,51 +57,84 @@ impl PartialOrd for Node {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
 }
 
-fn dijkstra<F>(input: Vec<String>, next: F) -> usize
+fn parse(input: &str) -> Result<Grid<usize>, ParseError> {
+    Grid::parse_with(input, |c| {
+        c.to_digit(10)
+            .map(|digit| digit as usize)
+            .ok_or_else(|| format!("expected a digit, found `{c}`"))
+    })
+}
+
+/// Least heat lost on the way from the top left block to the bottom right
+/// one, or an error if there is no way.
+fn dijkstra<F>(grid: &Grid<usize>, next: F) -> Result<usize, String>
 where
-    F: Fn(Node, &Vec<Vec<usize>>) -> Vec<Node>,
+    F: Fn(Node, &Grid<usize>) -> Vec<Node>,
 {
-    let grid: Vec<Vec<usize>> = input
-        .iter()
-        .map(|l| {
-            l.chars()
-                .map(|i| i.to_digit(10).unwrap() as usize)
-                .collect()
-        })
-        .collect();
-
-    let v = grid.len();
-    let h = grid[0].len();
+    if grid.width() == 0 || grid.height() == 0 {
+        return Err("the city is empty".to_string());
+    }
+    let start = Coord::new(0, 0);
+    let end = Coord::new(grid.height() - 1, grid.width() - 1);
+    if start == end {
+        return Ok(0);
+    }
 
     let mut heap = BinaryHeap::new();
     let mut visited = HashSet::new();
 
-    heap.push(Node {
-        coord: Coord(1, 0),
-        cost: grid[1][0],
-        last: Direction::Down,
-        repeat: 1,
-    });
-    heap.push(Node {
-        coord: Coord(0, 1),
-        cost: grid[0][1],
-        last: Direction::Right,
-        repeat: 1,
-    });
+    for direction in [Direction::Down, Direction::Right] {
+        if let Some(coord) = grid.step(start, direction) {
+            heap.push(Node {
+                coord,
+                cost: grid[coord],
+                last: direction,
+                repeat: 1,
+            });
+        }
+    }
 
     while let Some(node) = heap.pop() {
-        if node.coord == Coord(v - 1, h - 1) {
-            return node.cost;
+        checkpoint();
+        if node.coord == end {
+            return Ok(node.cost);
         }
-        next(node, &grid).iter().for_each(|&n| {
+        next(node, grid).iter().for_each(|&n| {
             if visited.insert((n.coord, n.last, n.repeat)) {
                 heap.push(n)
             }
         });
     }
-    usize::MAX
+    Err("the crucible cannot reach the end".to_string())
+}
+
+pub fn solution1(input: &Grid<usize>) -> Result<usize, String> {
+    dijkstra(input, |n, grid| n.next(grid, false))
+}
+
+pub fn solution2(input: &Grid<usize>) -> Result<usize, String> {
+    dijkstra(input, |n, grid| n.next(grid, true))
 }
 
-pub fn solution1(input: Vec<String>) -> usize { dijkstra(input, |n, grid| n.next(grid, false)) }
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Model = Grid<usize>;
+    type Output = Result<usize, String>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day17.txt"),
+        part1: Some(Answer::Unsigned(102)),
+        part2: Some(Answer::Unsigned(94)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 17,
+        title: "Clumsy Crucible",
+    };
 
-pub fn solution2(input: Vec<String>) -> usize { dijkstra(input, |n, grid| n.next(grid, true)) }
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse(input.text()) }
+
+    fn part1(input: &Self::Model) -> Result<usize, String> { solution1(input) }
+
+    fn part2(input: &Self::Model) -> Result<usize, String> { solution2(input) }
+}
@@ -1,116 +1,115 @@
-use itertools::Itertools;
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::geometry::{Direction, Point};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day18.pest"]
 pub struct TrenchParser;
 
-#[derive(Debug, Copy, Clone)]
-enum Horizontal {
-    Left,
-    Right,
-    None,
-}
-
-#[derive(Debug, Copy, Clone)]
-enum Vertical {
-    Up,
-    Down,
-    None,
-}
+/// Area of the lagoon dug by following `instructions`, edges included, or an
+/// error when its coordinates or area overflow.
+pub fn dig(instructions: Vec<(Direction, i64)>) -> Result<usize, String> {
+    let overflow = || "the lagoon is too large to measure".to_string();
+    let mut start = Point::default();
+    let mut twice_area: i64 = 0;
+    let mut perimeter: i64 = 0;
 
-#[derive(Debug, Copy, Clone)]
-
-struct Direction(Horizontal, Vertical);
-
-impl From<&str> for Direction {
-    fn from(value: &str) -> Self {
-        match value {
-            "R" | "0" => Direction(Horizontal::Right, Vertical::None),
-            "D" | "1" => Direction(Horizontal::None, Vertical::Down),
-            "L" | "2" => Direction(Horizontal::Left, Vertical::None),
-            "U" | "3" => Direction(Horizontal::None, Vertical::Up),
-            _ => unreachable!(),
-        }
+    for (direction, distance) in instructions {
+        let end = start.step(direction, distance).ok_or_else(overflow)?;
+        let cross = start
+            .row
+            .checked_mul(end.column)
+            .zip(end.row.checked_mul(start.column))
+            .and_then(|(a, b)| a.checked_sub(b))
+            .ok_or_else(overflow)?;
+        twice_area = twice_area.checked_add(cross).ok_or_else(overflow)?;
+        perimeter = perimeter.checked_add(distance).ok_or_else(overflow)?;
+        start = end;
     }
+
+    let area = twice_area.unsigned_abs() / 2;
+    area.checked_add(perimeter.unsigned_abs() / 2 + 1)
+        .and_then(|area| usize::try_from(area).ok())
+        .ok_or_else(overflow)
 }
 
-fn dig(instructions: Vec<(Direction, i64)>) -> usize {
-    let mut start = (0, 0);
-    let mut vertices = vec![(0, 0)];
-    let mut perimeter = 0;
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub distance: i64,
+    /// Hex code between parenthesis, such as `(#70c710)`.
+    pub color: String,
+}
 
-    for (direction, distance) in instructions {
-        match direction {
-            Direction(_, Vertical::Up) => {
-                vertices.push((start.0 - distance, start.1));
-                start = (start.0 - distance, start.1);
-            },
-            Direction(_, Vertical::Down) => {
-                vertices.push((start.0 + distance, start.1));
-                start = (start.0 + distance, start.1);
-            },
-            Direction(Horizontal::Left, _) => {
-                vertices.push((start.0, start.1 - distance));
-                start = (start.0, start.1 - distance);
-            },
-            Direction(Horizontal::Right, _) => {
-                vertices.push((start.0, start.1 + distance));
-                start = (start.0, start.1 + distance);
-            },
-            _ => unreachable!(),
-        }
-        perimeter += distance as usize;
+impl Instruction {
+    /// Swap the direction and distance by the ones encoded in the color.
+    fn invert(&self) -> (Direction, i64) {
+        let hexcode = &self.color;
+        let direction = hexcode[7..8].parse().unwrap();
+        let distance = i64::from_str_radix(&hexcode[2..7], 16).unwrap();
+        (direction, distance)
     }
-
-    let area: usize = vertices
-        .iter()
-        .tuple_windows()
-        .map(|((xa, ya), (xb, yb))| (xa * yb - xb * ya))
-        .sum::<i64>()
-        .unsigned_abs() as usize
-        >> 1;
-
-    area + perimeter / 2 + 1
 }
 
-fn parse(input: String, invert: bool) -> (Direction, i64) {
-    let mut pairs = TrenchParser::parse(Rule::trench, &input)
-        .unwrap()
+fn parse(input: &str) -> Result<Instruction, ParseError> {
+    let mut pairs = TrenchParser::parse(Rule::trench, input)?
         .next()
         .unwrap()
         .into_inner();
-    if !invert {
-        let direction: Direction = pairs.next().unwrap().as_str().into();
-        let distance: i64 = pairs.next().unwrap().as_str().parse().unwrap();
-        (direction, distance)
-    } else {
-        let hexcode = pairs.nth(2).unwrap().as_str();
-        let direction = hexcode[7..8].into();
-        let distance = i64::from_str_radix(&hexcode[2..7], 16).unwrap();
-        (direction, distance)
-    }
+    let direction: Direction = pairs.next().unwrap().as_str().parse().unwrap();
+    let distance: i64 = parse_number(&pairs.next().unwrap())?;
+    let color = pairs.next().unwrap().as_str().to_string();
+    Ok(Instruction {
+        direction,
+        distance,
+        color,
+    })
 }
 
-pub fn solution1(input: Vec<String>) -> usize {
+pub fn solution1(input: &[Instruction]) -> Result<usize, String> {
     let mut instructions = Vec::new();
 
     for line in input {
-        let line = parse(line, false);
-        instructions.push(line);
+        instructions.push((line.direction, line.distance));
     }
 
     dig(instructions)
 }
 
-pub fn solution2(input: Vec<String>) -> usize {
+pub fn solution2(input: &[Instruction]) -> Result<usize, String> {
     let mut instructions = Vec::new();
 
     for line in input {
-        let line = parse(line, true);
-        instructions.push(line);
+        instructions.push(line.invert());
     }
 
     dig(instructions)
 }
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Model = Vec<Instruction>;
+    type Output = Result<usize, String>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day18.txt"),
+        part1: Some(Answer::Unsigned(62)),
+        part2: Some(Answer::Unsigned(952408144115)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 18,
+        title: "Lavaduct Lagoon",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse_lines(input.lines(), parse) }
+
+    fn part1(input: &Self::Model) -> Result<usize, String> { solution1(input) }
+
+    fn part2(input: &Self::Model) -> Result<usize, String> { solution2(input) }
+}
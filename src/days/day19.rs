@@ -3,18 +3,24 @@ use std::collections::HashMap;
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::error::{parse_number, ParseError};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day19.pest"]
 struct RatingsParser;
 
+/// Where a part goes once a step applies to it.
 #[derive(Debug, Clone)]
-enum Result {
+pub enum Target {
     Accept,
     Reject,
     Rule(String),
 }
 
-impl From<&str> for Result {
+impl From<&str> for Target {
     fn from(value: &str) -> Self {
         match value {
             "A" => Self::Accept,
@@ -25,21 +31,28 @@ impl From<&str> for Result {
 }
 
 #[derive(Debug, Clone)]
-enum Step {
-    Less(String, u64, Result),
-    Greater(String, u64, Result),
-    Final(Result),
+pub enum Step {
+    Less(String, u64, Target),
+    Greater(String, u64, Target),
+    Final(Target),
 }
 
 impl From<&str> for Step {
-    fn from(value: &str) -> Self {
-        Self::Final(value.into())
-    }
+    fn from(value: &str) -> Self { Self::Final(value.into()) }
 }
 
-fn parse(input: String) -> (HashMap<String, Vec<Step>>, Vec<HashMap<String, u64>>) {
-    let pairs = RatingsParser::parse(Rule::ratings, &input)
-        .unwrap()
+pub type Workflows = HashMap<String, Vec<Step>>;
+/// Ratings of a part, by category.
+pub type Ratings = HashMap<String, u64>;
+
+#[derive(Debug, Clone)]
+pub struct System {
+    pub workflows: Workflows,
+    pub parts: Vec<Ratings>,
+}
+
+fn parse(input: &str) -> Result<System, ParseError> {
+    let pairs = RatingsParser::parse(Rule::ratings, input)?
         .next()
         .unwrap()
         .into_inner();
@@ -59,7 +72,7 @@ fn parse(input: String) -> (HashMap<String, Vec<Step>>, Vec<HashMap<String, u64>
                                 let mut pairs = pair.into_inner(); // (code condition number next)
                                 let piece = pairs.next().unwrap().as_str().to_string();
                                 let condition = pairs.next().unwrap().as_str();
-                                let value: u64 = pairs.next().unwrap().as_str().parse().unwrap();
+                                let value: u64 = parse_number(&pairs.next().unwrap())?;
                                 let next = pairs.next().unwrap().as_str();
                                 match condition {
                                     "<" => steps.push(Step::Less(piece, value, next.into())),
@@ -82,7 +95,7 @@ fn parse(input: String) -> (HashMap<String, Vec<Step>>, Vec<HashMap<String, u64>
                     for pair in pairs {
                         let mut pairs = pair.into_inner(); // (code number)
                         let code = pairs.next().unwrap().as_str().to_string();
-                        let number: u64 = pairs.next().unwrap().as_str().parse().unwrap();
+                        let number: u64 = parse_number(&pairs.next().unwrap())?;
                         part.insert(code, number);
                     }
                     parts.push(part);
@@ -92,11 +105,11 @@ fn parse(input: String) -> (HashMap<String, Vec<Step>>, Vec<HashMap<String, u64>
             _ => unreachable!(),
         };
     }
-    (workflows, parts)
+    Ok(System { workflows, parts })
 }
 
-pub fn solution1(input: Vec<String>) -> u64 {
-    let (workflows, parts) = parse(input.join("\n"));
+pub fn solution1(input: &System) -> u64 {
+    let System { workflows, parts } = input;
     let mut rating = 0;
     'outer: for part in parts {
         let mut workflow = "in".to_string();
@@ -124,14 +137,14 @@ pub fn solution1(input: Vec<String>) -> u64 {
                     },
                 }
                 match next_workflow {
-                    Result::Accept => {
+                    Target::Accept => {
                         rating += part.values().sum::<u64>();
                         continue 'outer;
                     },
-                    Result::Reject => {
+                    Target::Reject => {
                         continue 'outer;
                     },
-                    Result::Rule(next) => {
+                    Target::Rule(next) => {
                         workflow = next.to_string();
                         continue 'middle;
                     },
@@ -150,7 +163,9 @@ struct PieceGroup {
     s: (u64, u64),
 }
 
-fn split_range(range: (u64, u64), value: u64) -> (Option<(u64, u64)>, Option<(u64, u64)>) {
+type Interval = (u64, u64);
+
+fn split_range(range: Interval, value: u64) -> (Option<Interval>, Option<Interval>) {
     if range.0 >= value {
         return (None, Some(range));
     }
@@ -166,27 +181,27 @@ impl PieceGroup {
         match rating.as_str() {
             "x" => {
                 let (x1, x2) = split_range(x, value);
-                let s1 = if let Some(x) = x1 { Some(Self { x, m, a, s }) } else { None };
-                let s2 = if let Some(x) = x2 { Some(Self { x, m, a, s }) } else { None };
-                return (s1, s2);
+                let s1 = x1.map(|x| Self { x, m, a, s });
+                let s2 = x2.map(|x| Self { x, m, a, s });
+                (s1, s2)
             },
             "m" => {
                 let (m1, m2) = split_range(m, value);
-                let s1 = if let Some(m) = m1 { Some(Self { x, m, a, s }) } else { None };
-                let s2 = if let Some(m) = m2 { Some(Self { x, m, a, s }) } else { None };
-                return (s1, s2);
+                let s1 = m1.map(|m| Self { x, m, a, s });
+                let s2 = m2.map(|m| Self { x, m, a, s });
+                (s1, s2)
             },
             "a" => {
                 let (a1, a2) = split_range(a, value);
-                let s1 = if let Some(a) = a1 { Some(Self { x, m, a, s }) } else { None };
-                let s2 = if let Some(a) = a2 { Some(Self { x, m, a, s }) } else { None };
-                return (s1, s2);
+                let s1 = a1.map(|a| Self { x, m, a, s });
+                let s2 = a2.map(|a| Self { x, m, a, s });
+                (s1, s2)
             },
             "s" => {
                 let (s1, s2) = split_range(s, value);
-                let s1 = if let Some(s) = s1 { Some(Self { x, m, a, s }) } else { None };
-                let s2 = if let Some(s) = s2 { Some(Self { x, m, a, s }) } else { None };
-                return (s1, s2);
+                let s1 = s1.map(|s| Self { x, m, a, s });
+                let s2 = s2.map(|s| Self { x, m, a, s });
+                (s1, s2)
             },
             _ => unreachable!(),
         }
@@ -198,8 +213,8 @@ impl PieceGroup {
     }
 }
 
-pub fn solution2(input: Vec<String>) -> u64 {
-    let (workflows, _) = parse(input.join("\n"));
+pub fn solution2(input: &System) -> u64 {
+    let workflows = &input.workflows;
     let mut groups = vec![(
         PieceGroup {
             x: (1, 4000),
@@ -218,9 +233,9 @@ pub fn solution2(input: Vec<String>) -> u64 {
                     let (left, right) = group.split(key.to_string(), *value);
                     if let Some(left) = left {
                         match next {
-                            Result::Accept => accepted += left.total(),
-                            Result::Reject => (),
-                            Result::Rule(next) => groups.push((left, next.to_string())),
+                            Target::Accept => accepted += left.total(),
+                            Target::Reject => (),
+                            Target::Rule(next) => groups.push((left, next.to_string())),
                         }
                     }
                     if let Some(right) = right {
@@ -231,20 +246,43 @@ pub fn solution2(input: Vec<String>) -> u64 {
                     let (left, right) = group.split(key.to_string(), *value + 1);
                     if let Some(right) = right {
                         match next {
-                            Result::Accept => accepted += right.total(),
-                            Result::Reject => (),
-                            Result::Rule(next) => groups.push((right, next.to_string())),
+                            Target::Accept => accepted += right.total(),
+                            Target::Reject => (),
+                            Target::Rule(next) => groups.push((right, next.to_string())),
                         }
                     }
                     if let Some(left) = left {
                         group = left;
                     }
                 },
-                Step::Final(Result::Accept) => accepted += group.total(),
-                Step::Final(Result::Reject) => (),
-                Step::Final(Result::Rule(next)) => groups.push((group, next.to_string())),
+                Step::Final(Target::Accept) => accepted += group.total(),
+                Step::Final(Target::Reject) => (),
+                Step::Final(Target::Rule(next)) => groups.push((group, next.to_string())),
             }
         }
     }
     accepted
 }
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Model = System;
+    type Output = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day19.txt"),
+        part1: Some(Answer::Unsigned(19114)),
+        part2: Some(Answer::Unsigned(167409079868000)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 19,
+        title: "Aplenty",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse(input.text()) }
+
+    fn part1(input: &Self::Model) -> u64 { solution1(input) }
+
+    fn part2(input: &Self::Model) -> u64 { solution2(input) }
+}
@@ -3,94 +3,120 @@ use std::cmp::max;
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day2.pest"]
 pub struct GamesParser;
 
-fn parse(input: String, power: bool) -> Option<u32> {
-    let mut pairs = GamesParser::parse(Rule::game, &input)
-        .unwrap()
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Set {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Set>,
+}
+
+fn parse(input: &str) -> Result<Game, ParseError> {
+    let mut pairs = GamesParser::parse(Rule::game, input)?
         .next()
         .unwrap()
         .into_inner();
 
-    let id = pairs.next().unwrap().as_str().parse().unwrap();
-    let mut red = 0;
-    let mut green = 0;
-    let mut blue = 0;
+    let id = parse_number(&pairs.next().unwrap())?;
+    let mut sets = Vec::new();
 
     for pair in pairs {
-        if !power {
-            red = 0;
-            green = 0;
-            blue = 0;
-        }
-
         match pair.as_rule() {
             Rule::set => {
                 let pairs = pair.into_inner();
+                let mut set = Set::default();
                 let mut number = 0;
                 for pair in pairs {
                     match pair.as_rule() {
-                        Rule::number => number = pair.as_str().parse().unwrap(),
-                        Rule::color => {
-                            let color = pair.as_str();
-
-                            if !power {
-                                match color {
-                                    "red" => red += number,
-                                    "green" => green += number,
-                                    "blue" => blue += number,
-                                    _ => unreachable!(),
-                                }
-                                number = 0;
-                            } else {
-                                match color {
-                                    "red" => red = max(red, number),
-                                    "green" => green = max(green, number),
-                                    "blue" => blue = max(blue, number),
-                                    _ => unreachable!(),
-                                }
-                            }
+                        Rule::number => number = parse_number(&pair)?,
+                        Rule::color => match pair.as_str() {
+                            "red" => set.red += number,
+                            "green" => set.green += number,
+                            "blue" => set.blue += number,
+                            _ => unreachable!(),
                         },
                         _ => unreachable!(),
                     }
                 }
+                sets.push(set);
             },
             Rule::EOI => (),
             _ => unreachable!(),
         };
-        if !power && (red > 12 || green > 13 || blue > 14) {
-            return None;
-        }
     }
 
-    if !power {
-        Some(id)
-    } else {
-        Some(red * green * blue)
-    }
+    Ok(Game { id, sets })
 }
 
-pub fn solution1(input: Vec<String>) -> u32 {
+pub fn solution1(input: &[Game]) -> u32 {
     let mut output: Vec<u32> = Vec::new();
 
-    for line in input {
-        let id = parse(line, false);
-        if let Some(id) = id {
-            output.push(id);
+    for game in input {
+        if game
+            .sets
+            .iter()
+            .all(|set| set.red <= 12 && set.green <= 13 && set.blue <= 14)
+        {
+            output.push(game.id);
         }
     }
 
     output.iter().sum()
 }
 
-pub fn solution2(input: Vec<String>) -> u32 {
+pub fn solution2(input: &[Game]) -> u32 {
     let mut output: Vec<u32> = Vec::new();
 
-    for line in input {
-        output.push(parse(line, true).unwrap());
+    for game in input {
+        let mut red = 0;
+        let mut green = 0;
+        let mut blue = 0;
+
+        for set in &game.sets {
+            red = max(red, set.red);
+            green = max(green, set.green);
+            blue = max(blue, set.blue);
+        }
+
+        output.push(red * green * blue);
     }
 
     output.iter().sum()
 }
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Model = Vec<Game>;
+    type Output = u32;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day2.txt"),
+        part1: Some(Answer::Unsigned(8)),
+        part2: Some(Answer::Unsigned(2286)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 2,
+        title: "Cube Conundrum",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse_lines(input.lines(), parse) }
+
+    fn part1(input: &Self::Model) -> u32 { solution1(input) }
+
+    fn part2(input: &Self::Model) -> u32 { solution2(input) }
+}
@@ -1,3 +0,0 @@
-pub fn solution1(_input: Vec<String>) { todo!() }
-
-pub fn solution2(_input: Vec<String>) { todo!() }
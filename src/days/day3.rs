@@ -1,16 +1,22 @@
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::error::{parse_number, ParseError};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day3.pest"]
 pub struct GamesParser;
 
-struct Numbers {
-    number: usize,
-    top: usize,
-    left: usize,
-    bottom: usize,
-    right: usize,
+#[derive(Debug, Clone, Copy)]
+pub struct Numbers {
+    pub number: usize,
+    pub top: usize,
+    pub left: usize,
+    pub bottom: usize,
+    pub right: usize,
 }
 
 impl Numbers {
@@ -23,12 +29,18 @@ impl Numbers {
     }
 }
 
-fn parser(input: String, idx: usize, gear: bool) -> (Vec<Numbers>, Vec<(usize, usize)>) {
+#[derive(Debug, Clone, Default)]
+pub struct Schematic {
+    pub numbers: Vec<Numbers>,
+    pub symbols: Vec<(usize, usize)>,
+}
+
+fn parser(input: &str, idx: usize) -> Result<Schematic, ParseError> {
     let top = if idx == 0 { 0 } else { idx - 1 };
     let bottom = idx + 1;
 
-    let pairs = GamesParser::parse(Rule::schematic, &input)
-        .unwrap()
+    let pairs = GamesParser::parse(Rule::schematic, input)
+        .map_err(|e| ParseError::from(e).offset(idx))?
         .next()
         .unwrap()
         .into_inner();
@@ -42,7 +54,7 @@ fn parser(input: String, idx: usize, gear: bool) -> (Vec<Numbers>, Vec<(usize, u
                 for pair in pairs {
                     match pair.as_rule() {
                         Rule::number => {
-                            let number: usize = pair.as_str().parse().unwrap();
+                            let number: usize = parse_number(&pair).map_err(|e| e.offset(idx))?;
                             let span = pair.as_span();
                             let mut start: usize = span.start();
                             start = if start == 0 { 0 } else { start - 1 };
@@ -56,9 +68,6 @@ fn parser(input: String, idx: usize, gear: bool) -> (Vec<Numbers>, Vec<(usize, u
                             });
                         },
                         Rule::symbol => {
-                            if gear && pair.as_str() != "*" {
-                                continue;
-                            }
                             let span = pair.as_span();
                             let start: usize = span.start();
                             symbols.push((idx, start));
@@ -72,23 +81,26 @@ fn parser(input: String, idx: usize, gear: bool) -> (Vec<Numbers>, Vec<(usize, u
         };
     }
 
-    (numbers, symbols)
+    Ok(Schematic { numbers, symbols })
 }
 
-pub fn solution1(input: Vec<String>) -> usize {
-    let mut value: usize = 0;
-
-    let mut numbers = Vec::new();
-    let mut symbols = Vec::new();
+fn parse<'a>(input: impl Iterator<Item = &'a str>) -> Result<Schematic, ParseError> {
+    let mut schematic = Schematic::default();
 
-    for (i, line) in input.iter().enumerate() {
-        let (mut n, mut s) = parser(line.to_string(), i, false);
-        numbers.append(&mut n);
-        symbols.append(&mut s);
+    for (i, line) in input.enumerate() {
+        let mut line = parser(line, i)?;
+        schematic.numbers.append(&mut line.numbers);
+        schematic.symbols.append(&mut line.symbols);
     }
 
-    for number in numbers {
-        for &symbol in &symbols {
+    Ok(schematic)
+}
+
+pub fn solution1(input: &Schematic) -> usize {
+    let mut value: usize = 0;
+
+    for number in &input.numbers {
+        for &symbol in &input.symbols {
             if let Some(v) = number.in_range(symbol) {
                 value += v;
                 break;
@@ -99,21 +111,12 @@ pub fn solution1(input: Vec<String>) -> usize {
     value
 }
 
-pub fn solution2(input: Vec<String>) -> usize {
+pub fn solution2(input: &Schematic) -> usize {
     let mut value: usize = 0;
 
-    let mut numbers = Vec::new();
-    let mut symbols = Vec::new();
-
-    for (i, line) in input.iter().enumerate() {
-        let (mut n, mut s) = parser(line.to_string(), i, false);
-        numbers.append(&mut n);
-        symbols.append(&mut s);
-    }
-
-    for &symbol in &symbols {
+    for &symbol in &input.symbols {
         let mut val = Vec::new();
-        for number in &numbers {
+        for number in &input.numbers {
             if let Some(v) = number.in_range(symbol) {
                 val.push(v);
             }
@@ -125,3 +128,26 @@ pub fn solution2(input: Vec<String>) -> usize {
 
     value
 }
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Model = Schematic;
+    type Output = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day3.txt"),
+        part1: Some(Answer::Unsigned(4361)),
+        part2: Some(Answer::Unsigned(467835)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 3,
+        title: "Gear Ratios",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse(input.lines()) }
+
+    fn part1(input: &Self::Model) -> usize { solution1(input) }
+
+    fn part2(input: &Self::Model) -> usize { solution2(input) }
+}
@@ -1,13 +1,37 @@
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day4.pest"]
 pub struct CardsParser;
 
-fn parser(input: String) -> usize {
-    let pairs = CardsParser::parse(Rule::card, &input)
-        .unwrap()
+#[derive(Debug, Clone)]
+pub struct Card {
+    pub win: Vec<u32>,
+    pub mine: Vec<u32>,
+}
+
+impl Card {
+    /// Number of winning numbers present in the card.
+    pub fn matches(&self) -> usize {
+        let mut pow = 0;
+        for w in &self.win {
+            if self.mine.binary_search(w).is_ok() {
+                pow += 1;
+            }
+        }
+
+        pow
+    }
+}
+
+fn parser(input: &str) -> Result<Card, ParseError> {
+    let pairs = CardsParser::parse(Rule::card, input)?
         .next()
         .unwrap()
         .into_inner();
@@ -21,7 +45,7 @@ fn parser(input: String) -> usize {
                 let pairs = pair.into_inner();
                 for pair in pairs {
                     match pair.as_rule() {
-                        Rule::number => win.push(pair.as_str().parse().unwrap()),
+                        Rule::number => win.push(parse_number(&pair)?),
                         _ => unreachable!(),
                     }
                 }
@@ -30,7 +54,7 @@ fn parser(input: String) -> usize {
                 let pairs = pair.into_inner();
                 for pair in pairs {
                     match pair.as_rule() {
-                        Rule::number => mine.push(pair.as_str().parse().unwrap()),
+                        Rule::number => mine.push(parse_number(&pair)?),
                         _ => unreachable!(),
                     }
                 }
@@ -43,21 +67,14 @@ fn parser(input: String) -> usize {
     win.sort_unstable();
     mine.sort_unstable();
 
-    let mut pow = 0;
-    for w in win {
-        if mine.binary_search(&w).is_ok() {
-            pow += 1;
-        }
-    }
-
-    pow
+    Ok(Card { win, mine })
 }
 
-pub fn solution1(input: Vec<String>) -> usize {
+pub fn solution1(input: &[Card]) -> usize {
     let mut value: usize = 0;
 
-    for line in input {
-        let pow = parser(line);
+    for card in input {
+        let pow = card.matches();
         if pow != 0 {
             value += 2_usize.pow((pow - 1).try_into().unwrap())
         }
@@ -66,11 +83,11 @@ pub fn solution1(input: Vec<String>) -> usize {
     value
 }
 
-pub fn solution2(input: Vec<String>) -> usize {
+pub fn solution2(input: &[Card]) -> usize {
     let mut rep: Vec<usize> = vec![1; input.len()];
 
-    for (i, line) in input.iter().enumerate() {
-        let val = parser(line.to_string());
+    for (i, card) in input.iter().enumerate() {
+        let val = card.matches();
         if val != 0 {
             for j in i + 1..i + 1 + val {
                 if j < rep.len() {
@@ -84,3 +101,28 @@ pub fn solution2(input: Vec<String>) -> usize {
 
     rep.iter().sum()
 }
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Model = Vec<Card>;
+    type Output = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day4.txt"),
+        part1: Some(Answer::Unsigned(13)),
+        part2: Some(Answer::Unsigned(30)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 4,
+        title: "Scratchcards",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> {
+        parse_lines(input.lines(), parser)
+    }
+
+    fn part1(input: &Self::Model) -> usize { solution1(input) }
+
+    fn part2(input: &Self::Model) -> usize { solution2(input) }
+}
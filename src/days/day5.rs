@@ -3,20 +3,26 @@ use std::collections::VecDeque;
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::error::{parse_number, ParseError};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day5.pest"]
 pub struct AlmanacParser;
 
-struct Range {
-    src: u64,
-    dst: u64,
-    range: u64,
+#[derive(Debug, Clone, Copy)]
+pub struct Range {
+    pub src: u64,
+    pub dst: u64,
+    pub range: u64,
 }
 
 type RangePair = (Vec<(u64, u64)>, Vec<(u64, u64)>);
 
 impl Range {
-    fn src_to_dst(&self, src: u64) -> Option<u64> {
+    pub fn src_to_dst(&self, src: u64) -> Option<u64> {
         if src >= self.src && src < self.src + self.range {
             Some(self.dst + src - self.src)
         } else {
@@ -24,7 +30,7 @@ impl Range {
         }
     }
 
-    fn range_match(&self, (src, range): (u64, u64)) -> RangePair {
+    pub fn range_match(&self, (src, range): (u64, u64)) -> RangePair {
         let mut matched = Vec::new();
         let mut not_matched = Vec::new();
         //  (  ) [  ] or [  ] (  )
@@ -61,11 +67,14 @@ impl Range {
     }
 }
 
-// type
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<Vec<Range>>,
+}
 
-fn parse(input: String) -> (Vec<u64>, Vec<Vec<Range>>) {
-    let mut pairs = AlmanacParser::parse(Rule::almanac, &input)
-        .unwrap()
+fn parse(input: &str) -> Result<Almanac, ParseError> {
+    let mut pairs = AlmanacParser::parse(Rule::almanac, input)?
         .next()
         .unwrap()
         .into_inner();
@@ -74,7 +83,7 @@ fn parse(input: String) -> (Vec<u64>, Vec<Vec<Range>>) {
 
     for pair in pairs.next().unwrap().into_inner() {
         match pair.as_rule() {
-            Rule::number => seeds.push(pair.as_str().parse().unwrap()),
+            Rule::number => seeds.push(parse_number(&pair)?),
             _ => unreachable!("pairwise"),
         }
     }
@@ -91,9 +100,9 @@ fn parse(input: String) -> (Vec<u64>, Vec<Vec<Range>>) {
                         Rule::range => {
                             let mut pairs = pair.into_inner();
 
-                            let dst = pairs.next().unwrap().as_str().parse().unwrap();
-                            let src = pairs.next().unwrap().as_str().parse().unwrap();
-                            let range = pairs.next().unwrap().as_str().parse().unwrap();
+                            let dst = parse_number(&pairs.next().unwrap())?;
+                            let src = parse_number(&pairs.next().unwrap())?;
+                            let range = parse_number(&pairs.next().unwrap())?;
 
                             map.push(Range { src, dst, range })
                         },
@@ -107,18 +116,15 @@ fn parse(input: String) -> (Vec<u64>, Vec<Vec<Range>>) {
         maps.push(map)
     }
 
-    (seeds, maps)
+    Ok(Almanac { seeds, maps })
 }
 
-pub fn solution1(input: Vec<String>) -> u64 {
+pub fn solution1(input: &Almanac) -> u64 {
     let mut output = Vec::new();
 
-    let input = input.join("\n");
-    let (seeds, maps) = parse(input);
-
-    for seed in seeds {
+    for &seed in &input.seeds {
         let mut mapped = seed;
-        for map in &maps {
+        for map in &input.maps {
             for range in map {
                 if let Some(dst) = range.src_to_dst(mapped) {
                     mapped = dst;
@@ -133,17 +139,15 @@ pub fn solution1(input: Vec<String>) -> u64 {
     output.iter().min().unwrap().to_owned()
 }
 
-pub fn solution2(input: Vec<String>) -> u64 {
-    let input = input.join("\n");
-    let (seeds, maps) = parse(input);
-
-    let mut mapped: VecDeque<(u64, u64)> = seeds
+pub fn solution2(input: &Almanac) -> u64 {
+    let mut mapped: VecDeque<(u64, u64)> = input
+        .seeds
         .chunks(2)
         .map(|s| if let &[a, b] = s { (a, b) } else { (0_u64, 0_u64) })
         .collect();
     let mut next_mapped = Vec::new();
 
-    for map in &maps {
+    for map in &input.maps {
         'outer: while let Some(mut first) = mapped.pop_front() {
             for range in map {
                 let (mut matched, not_matched) = range.range_match(first);
@@ -170,3 +174,69 @@ pub fn solution2(input: Vec<String>) -> u64 {
 
     mapped.iter().map(|(s, _)| s).min().unwrap().to_owned()
 }
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Model = Almanac;
+    type Output = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day5.txt"),
+        part1: Some(Answer::Unsigned(35)),
+        part2: Some(Answer::Unsigned(46)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 5,
+        title: "If You Give A Seed A Fertilizer",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse(input.text()) }
+
+    fn part1(input: &Self::Model) -> u64 { solution1(input) }
+
+    fn part2(input: &Self::Model) -> u64 { solution2(input) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RANGE: Range = Range {
+        src: 10,
+        dst: 100,
+        range: 10,
+    };
+
+    #[test]
+    fn range_match_disjoint() {
+        assert_eq!(RANGE.range_match((0, 5)), (vec![], vec![(0, 5)]));
+        assert_eq!(RANGE.range_match((0, 10)), (vec![], vec![(0, 10)]));
+        assert_eq!(RANGE.range_match((20, 5)), (vec![], vec![(20, 5)]));
+    }
+
+    #[test]
+    fn range_match_inside() {
+        assert_eq!(RANGE.range_match((12, 3)), (vec![(102, 3)], vec![]));
+        assert_eq!(RANGE.range_match((10, 10)), (vec![(100, 10)], vec![]));
+    }
+
+    #[test]
+    fn range_match_overlap() {
+        assert_eq!(RANGE.range_match((5, 10)), (vec![(100, 5)], vec![(5, 5)]));
+        assert_eq!(RANGE.range_match((5, 15)), (vec![(100, 10)], vec![(5, 5)]));
+        assert_eq!(RANGE.range_match((15, 10)), (vec![(105, 5)], vec![(20, 5)]));
+        assert_eq!(
+            RANGE.range_match((10, 15)),
+            (vec![(100, 10)], vec![(20, 5)])
+        );
+    }
+
+    #[test]
+    fn range_match_covering() {
+        assert_eq!(
+            RANGE.range_match((5, 20)),
+            (vec![(100, 10)], vec![(5, 5), (20, 5)])
+        );
+    }
+}
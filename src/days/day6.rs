@@ -2,90 +2,154 @@ use itertools::izip;
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::error::{parse_number, ParseError};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day6.pest"]
 pub struct CompetitionParser;
 
-fn parse(input: String, kerning: bool) -> Vec<(u64, u64)> {
-    let pairs = CompetitionParser::parse(Rule::competition, &input)
-        .unwrap()
+#[derive(Debug, Clone, Copy)]
+pub struct Race {
+    pub time: u64,
+    pub distance: u64,
+}
+
+fn parse(input: &str) -> Result<Vec<Race>, ParseError> {
+    let pairs = CompetitionParser::parse(Rule::competition, input)?
         .next()
         .unwrap()
         .into_inner();
     let mut times = Vec::new();
     let mut distances = Vec::new();
+    // End of each row, where a missing number would go.
+    let mut ends = Vec::new();
     for pair in pairs {
+        if pair.as_rule() != Rule::EOI {
+            ends.push(pair.as_span().end_pos());
+        }
         match pair.as_rule() {
             Rule::times => {
                 let pairs = pair.into_inner();
-                let mut number = String::new();
                 for pair in pairs {
                     match pair.as_rule() {
-                        Rule::number => {
-                            if !kerning {
-                                let number = pair.as_str().parse().unwrap();
-                                times.push(number);
-                            } else {
-                                number += pair.as_str();
-                            }
-                        },
+                        Rule::number => times.push(parse_number(&pair)?),
                         _ => unreachable!(),
                     }
                 }
-                if kerning {
-                    times.push(number.parse().unwrap());
-                }
             },
             Rule::distances => {
                 let pairs = pair.into_inner();
-                let mut number = String::new();
                 for pair in pairs {
                     match pair.as_rule() {
-                        Rule::number => {
-                            if !kerning {
-                                let number = pair.as_str().parse().unwrap();
-                                distances.push(number);
-                            } else {
-                                number += pair.as_str();
-                            }
-                        },
+                        Rule::number => distances.push(parse_number(&pair)?),
                         _ => unreachable!(),
                     }
                 }
-                if kerning {
-                    distances.push(number.parse().unwrap());
-                }
             },
             Rule::EOI => (),
             _ => unreachable!(),
         }
     }
-    izip!(times, distances).collect()
+    if times.len() != distances.len() {
+        let (shorter, found, expected) = if times.len() < distances.len() {
+            (&ends[0], times.len(), distances.len())
+        } else {
+            (&ends[1], distances.len(), times.len())
+        };
+        let (line, column) = shorter.line_col();
+        return Err(ParseError::new(
+            line,
+            column,
+            shorter.line_of().trim_end_matches(['\r', '\n']),
+            format!("expected {expected} numbers as in the other row, found {found}"),
+        ));
+    }
+    let races: Vec<Race> = izip!(times, distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect();
+
+    Ok(races)
+}
+
+/// Join the digits of `numbers`, or `None` if the result does not fit.
+fn kerning(numbers: impl Iterator<Item = u64>) -> Option<u64> {
+    numbers
+        .map(|n| n.to_string())
+        .collect::<String>()
+        .parse()
+        .ok()
 }
 
-pub fn solution1(input: Vec<String>) -> u64 {
-    let mut output = Vec::new();
+/// Number of ways to hold the button that beat the record of `race`, or
+/// `None` if the race is too long to compute it.
+pub fn ways(
+    Race {
+        time: t,
+        distance: d,
+    }: Race,
+) -> Option<u64> {
+    // `hold * (t - hold)` is at most `t * t / 4`, so it cannot overflow either.
+    let beats = |hold: u64| hold * (t - hold) > d;
+    let Some(discriminant) = t.checked_mul(t)?.checked_sub(d.checked_mul(4)?) else {
+        return Some(0);
+    };
 
-    let input = input.join("\n");
-    let pairs = parse(input, false);
+    // The roots only tie the record, and the float can be off by one, so
+    // walk to the first hold that actually beats it.
+    let delta = f64::sqrt(discriminant as f64);
+    let mut first = (((t as f64) - delta) / 2.0).floor() as u64;
+    while first > 0 && beats(first - 1) {
+        first -= 1;
+    }
+    while first <= t / 2 && !beats(first) {
+        first += 1;
+    }
 
-    for (t, d) in pairs {
-        let delta = f64::sqrt((t * t - 4 * d) as f64);
-        let first = (((t as f64) - delta) / 2.0).ceil() as u64;
-        let last = (((t as f64) + delta) / 2.0).floor() as u64;
-        output.push(last - first + 1);
+    // Holds are symmetric around t / 2.
+    if first > t / 2 {
+        Some(0)
+    } else {
+        Some(t + 1 - 2 * first)
     }
+}
+
+pub fn solution1(input: &[Race]) -> Result<u64, String> {
+    input
+        .iter()
+        .try_fold(1, |product: u64, &race| product.checked_mul(ways(race)?))
+        .ok_or_else(|| "too many ways to win the races to multiply them".to_string())
+}
+
+pub fn solution2(input: &[Race]) -> Result<u64, String> {
+    let too_large = || "numbers too large once joined into a single race".to_string();
+    let time = kerning(input.iter().map(|r| r.time)).ok_or_else(too_large)?;
+    let distance = kerning(input.iter().map(|r| r.distance)).ok_or_else(too_large)?;
 
-    output.iter().copied().reduce(|a, b| a * b).unwrap()
+    ways(Race { time, distance }).ok_or_else(too_large)
 }
 
-pub fn solution2(input: Vec<String>) -> u64 {
-    let input = input.join("\n");
-    let (t, d) = parse(input, true).first().unwrap().to_owned();
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Model = Vec<Race>;
+    type Output = Result<u64, String>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day6.txt"),
+        part1: Some(Answer::Unsigned(288)),
+        part2: Some(Answer::Unsigned(71503)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 6,
+        title: "Wait For It",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse(input.text()) }
 
-    let delta = f64::sqrt((t * t - 4 * d) as f64);
-    let first = (((t as f64) - delta) / 2.0).ceil() as u64;
-    let last = (((t as f64) + delta) / 2.0).floor() as u64;
+    fn part1(input: &Self::Model) -> Result<u64, String> { solution1(input) }
 
-    last - first + 1
+    fn part2(input: &Self::Model) -> Result<u64, String> { solution2(input) }
 }
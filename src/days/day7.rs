@@ -4,6 +4,11 @@ use itertools::Itertools;
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day7.pest"]
 pub struct BetParser;
@@ -95,14 +100,23 @@ enum Hand {
     Five,
 }
 
-fn parse(input: String, joker: bool) -> (Hand, Vec<char>, u64) {
-    let mut pairs = BetParser::parse(Rule::bet, &input)
-        .unwrap()
+#[derive(Debug, Clone)]
+pub struct Bet {
+    pub hand: Vec<char>,
+    pub bid: u64,
+}
+
+fn parse(input: &str) -> Result<Bet, ParseError> {
+    let mut pairs = BetParser::parse(Rule::bet, input)?
         .next()
         .unwrap()
         .into_inner();
     let hand = pairs.next().unwrap().as_str().chars().collect_vec();
-    let bet: u64 = pairs.next().unwrap().as_str().parse().unwrap();
+    let bid: u64 = parse_number(&pairs.next().unwrap())?;
+    Ok(Bet { hand, bid })
+}
+
+fn classify(hand: &[char], joker: bool) -> Hand {
     let cardcount: HashMap<char, u32> = hand
         .iter()
         .into_group_map_by(|&&x| x)
@@ -137,58 +151,73 @@ fn parse(input: String, joker: bool) -> (Hand, Vec<char>, u64) {
         || (double == 1 && jokers == 3)
         || jokers >= 4
     {
-        (Hand::Five, hand, bet)
+        Hand::Five
     } else if quadra || (triple && jokers == 1) || (double == 1 && jokers == 2) || jokers == 3 {
-        (Hand::Four, hand, bet)
+        Hand::Four
     } else if (triple && double == 1) || (double == 2 && jokers == 1) {
-        (Hand::Full, hand, bet)
+        Hand::Full
     } else if triple || (double == 1 && jokers == 1) || jokers == 2 {
-        (Hand::Three, hand, bet)
+        Hand::Three
     } else if double == 2 {
-        (Hand::DoublePair, hand, bet)
+        Hand::DoublePair
     } else if double == 1 || jokers == 1 {
-        (Hand::Pair, hand, bet)
+        Hand::Pair
     } else {
-        (Hand::High, hand, bet)
+        Hand::High
     }
 }
 
-pub fn solution1(input: Vec<String>) -> u64 {
-    let mut bets = Vec::new();
-
-    for line in input {
-        bets.push(parse(line, false));
-    }
+pub fn solution1(input: &[Bet]) -> u64 {
+    let mut bets = input.to_vec();
 
-    bets.sort_by_key(|(h, c, _)| {
+    bets.sort_by_key(|Bet { hand, .. }| {
         (
-            h.clone(),
-            c.iter().map(|&c| c.into()).collect::<Vec<Card>>(),
+            classify(hand, false),
+            hand.iter().map(|&c| c.into()).collect::<Vec<Card>>(),
         )
     });
 
     bets.iter()
         .enumerate()
-        .map(|(i, (_, _, b))| ((i + 1) as u64) * b)
+        .map(|(i, Bet { bid, .. })| ((i + 1) as u64) * bid)
         .sum()
 }
 
-pub fn solution2(input: Vec<String>) -> u64 {
-    let mut bets = Vec::new();
+pub fn solution2(input: &[Bet]) -> u64 {
+    let mut bets = input.to_vec();
 
-    for line in input {
-        bets.push(parse(line, true));
-    }
-
-    bets.sort_by_key(|(h, c, _)| {
+    bets.sort_by_key(|Bet { hand, .. }| {
         (
-            h.clone(),
-            c.iter().map(|&c| c.into()).collect::<Vec<JokerCard>>(),
+            classify(hand, true),
+            hand.iter().map(|&c| c.into()).collect::<Vec<JokerCard>>(),
         )
     });
 
     bets.iter()
         .enumerate()
-        .map(|(i, (_, _, b))| ((i + 1) as u64) * b)
+        .map(|(i, Bet { bid, .. })| ((i + 1) as u64) * bid)
         .sum()
 }
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Model = Vec<Bet>;
+    type Output = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day7.txt"),
+        part1: Some(Answer::Unsigned(6440)),
+        part2: Some(Answer::Unsigned(5905)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 7,
+        title: "Camel Cards",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse_lines(input.lines(), parse) }
+
+    fn part1(input: &Self::Model) -> u64 { solution1(input) }
+
+    fn part2(input: &Self::Model) -> u64 { solution2(input) }
+}
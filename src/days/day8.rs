@@ -1,55 +1,86 @@
-use std::collections::HashMap;
-use std::mem::swap;
+use std::collections::{HashMap, HashSet};
 
+use itertools::Itertools;
 use pest::Parser;
 use pest_derive::Parser as DeriveParser;
 
+use crate::answer::Answer;
+use crate::cancel::checkpoint;
+use crate::cycle::{self, Cycle};
+use crate::error::ParseError;
+use crate::geometry::Direction;
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
 #[derive(DeriveParser)]
 #[grammar = "days/day8.pest"]
 pub struct NetworkParser;
 
-#[derive(Clone)]
-enum Direction {
-    Left,
-    Right,
+#[derive(Debug, Clone)]
+pub struct Network {
+    pub directions: Vec<Direction>,
+    pub nodes: HashMap<String, (String, String)>,
 }
 
-fn parse(input: String) -> (Vec<Direction>, HashMap<String, (String, String)>) {
-    let mut pairs = NetworkParser::parse(Rule::network, &input)
-        .unwrap()
+fn parse(input: &str) -> Result<Network, ParseError> {
+    let mut pairs = NetworkParser::parse(Rule::network, input)?
         .next()
         .unwrap()
         .into_inner();
     let steps = pairs.next().unwrap().into_inner();
     let mut navigation = Vec::new();
     for s in steps {
-        match s.as_str() {
-            "L" => navigation.push(Direction::Left),
-            "R" => navigation.push(Direction::Right),
-            _ => unreachable!(),
-        }
+        navigation.push(s.as_str().parse().unwrap());
     }
     let mut network = HashMap::new();
+    let mut references = Vec::new();
     for pair in pairs {
         match pair.as_rule() {
             Rule::map => {
                 let mut pairs = pair.into_inner();
                 let current = pairs.next().unwrap().as_str().to_string();
-                let left = pairs.next().unwrap().as_str().to_string();
-                let right = pairs.next().unwrap().as_str().to_string();
-                network.insert(current, (left, right));
+                let left = pairs.next().unwrap();
+                let right = pairs.next().unwrap();
+                network.insert(
+                    current,
+                    (left.as_str().to_string(), right.as_str().to_string()),
+                );
+                references.extend([left, right]);
             },
             Rule::EOI => (),
             _ => unreachable!(),
         }
     }
-    (navigation, network)
+    // Following the network must never leave it.
+    if let Some(node) = references
+        .iter()
+        .find(|node| !network.contains_key(node.as_str()))
+    {
+        let (line, column) = node.line_col();
+        let text = node.as_span().start_pos().line_of();
+        return Err(ParseError::new(
+            line,
+            column,
+            text.trim_end_matches(['\r', '\n']),
+            format!("node `{}` is never defined", node.as_str()),
+        ));
+    }
+    Ok(Network {
+        directions: navigation,
+        nodes: network,
+    })
 }
 
-pub fn solution1(input: Vec<String>) -> u64 {
-    let input = input.join("\n");
-
-    let (directions, network) = parse(input);
+pub fn solution1(input: &Network) -> Result<u64, String> {
+    let Network {
+        directions,
+        nodes: network,
+    } = input;
+    for node in ["AAA", "ZZZ"] {
+        if !network.contains_key(node) {
+            return Err(format!("there is no node `{node}`"));
+        }
+    }
 
     let mut navigation = directions.iter();
     let mut start = "AAA".to_string();
@@ -57,73 +88,182 @@ pub fn solution1(input: Vec<String>) -> u64 {
 
     let mut steps = 0;
     while start != end {
+        checkpoint();
         if navigation.len() == 0 {
             navigation = directions.iter();
         }
 
         steps += 1;
 
-        match navigation.next().unwrap() {
-            Direction::Left => start = network[&start].0.clone(),
-            Direction::Right => start = network[&start].1.clone(),
-        }
+        start = follow(network, &start, *navigation.next().unwrap()).clone();
     }
 
-    steps
+    Ok(steps)
 }
 
-fn lcm(first: u64, second: u64) -> u64 { first * second / gcd(first, second) }
+/// Node reached by leaving `node` towards `direction`, which parsing checked
+/// to be defined.
+fn follow<'a>(
+    network: &'a HashMap<String, (String, String)>,
+    node: &str,
+    direction: Direction,
+) -> &'a String {
+    match direction {
+        Direction::Left => &network[node].0,
+        Direction::Right => &network[node].1,
+        Direction::Up | Direction::Down => unreachable!("the network only goes left or right"),
+    }
+}
 
-fn gcd(first: u64, second: u64) -> u64 {
-    let mut max = first;
-    let mut min = second;
+/// Greatest common divisor of `a` and `b`, along with `x` and `y` such that
+/// `a * x + b * y` is equal to it.
+fn euclid(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = euclid(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
 
-    if min > max {
-        swap(&mut min, &mut max);
+/// Merge two congruences `t = r (mod m)` into a single one, if they have any
+/// common solution.
+fn combine((r1, m1): (i128, i128), (r2, m2): (i128, i128)) -> Option<(i128, i128)> {
+    let (g, p, _) = euclid(m1, m2);
+    if (r2 - r1) % g != 0 {
+        return None;
     }
+    let m = m1 / g * m2;
+    let r = (r1 + (r2 - r1) / g * p % (m2 / g) * m1).rem_euclid(m);
+    Some((r, m))
+}
+
+/// Walk of a ghost through the network, which eventually loops.
+struct Ghost {
+    cycle: Cycle,
+    /// Steps before the end of the first loop at which the ghost is on a node
+    /// ending with `Z`.
+    ends: Vec<usize>,
+}
 
-    loop {
-        let res = max % min;
-        if res == 0 {
-            return min;
+impl Ghost {
+    fn new(
+        network: &HashMap<String, (String, String)>,
+        directions: &[Direction],
+        start: &str,
+    ) -> Self {
+        let next = |&(node, n): &(&str, usize)| {
+            (
+                follow(network, node, directions[n]).as_str(),
+                (n + 1) % directions.len(),
+            )
+        };
+        let cycle = cycle::brent(&(start, 0), next);
+
+        let mut ends = Vec::new();
+        let mut state = (start, 0);
+        for step in 0..cycle.tail + cycle.length {
+            if state.0.ends_with('Z') {
+                ends.push(step);
+            }
+            state = next(&state);
         }
+        Ghost { cycle, ends }
+    }
+
+    fn at_end(&self, step: usize) -> bool { self.ends.contains(&self.cycle.reduce(step)) }
 
-        max = min;
-        min = res;
+    /// Congruences on the steps at which the ghost is at an end once looping,
+    /// over the shortest period of these ends so that there are few of them.
+    fn looping_ends(&self) -> Vec<(i128, i128)> {
+        let length = self.cycle.length;
+        let ends: HashSet<usize> = self
+            .ends
+            .iter()
+            .filter(|&&step| step >= self.cycle.tail)
+            .map(|&step| step % length)
+            .collect();
+        let period = (1..=length)
+            .filter(|&period| length.is_multiple_of(period))
+            .find(|period| {
+                ends.iter()
+                    .all(|end| ends.contains(&((end + period) % length)))
+            })
+            .unwrap_or(length);
+
+        ends.iter()
+            .map(|end| end % period)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|end| (end as i128, period as i128))
+            .collect()
     }
 }
 
-pub fn solution2(input: Vec<String>) -> u64 {
-    let input = input.join("\n");
-
-    let (directions, network) = parse(input);
+pub fn solution2(input: &Network) -> Result<u64, String> {
+    let Network {
+        directions,
+        nodes: network,
+    } = input;
 
-    let start: Vec<String> = network
+    let ghosts: Vec<Ghost> = network
         .keys()
         .filter(|&k| k.ends_with('A'))
-        .cloned()
+        .map(|start| Ghost::new(network, directions, start))
         .collect();
+    if ghosts.is_empty() {
+        return Err("there is no node ending with `A`".to_string());
+    }
 
-    let mut steps = Vec::new();
+    let tail = ghosts.iter().map(|g| g.cycle.tail).max().unwrap_or(0);
+    if let Some(step) = (0..tail).find(|&step| ghosts.iter().all(|g| g.at_end(step))) {
+        return Ok(step as u64);
+    }
 
-    for mut s in start {
-        let mut navigation = directions.iter();
-        let mut step = 0;
+    // Past every tail, each ghost is at an end on one of its looping steps.
+    let tail = tail as i128;
+    ghosts
+        .iter()
+        .map(Ghost::looping_ends)
+        .multi_cartesian_product()
+        .filter_map(|ends| ends.into_iter().try_fold((0, 1), combine))
+        .map(|(r, m)| if r >= tail { r } else { r + (tail - r + m - 1) / m * m })
+        .min()
+        .map(|step| step as u64)
+        .ok_or_else(|| "the ghosts are never all at an end at once".to_string())
+}
 
-        while !s.ends_with('Z') {
-            if navigation.len() == 0 {
-                navigation = directions.iter();
-            }
+pub struct Day8;
 
-            step += 1;
+impl Solution for Day8 {
+    type Model = Network;
+    type Output = Result<u64, String>;
 
-            match navigation.next().unwrap() {
-                Direction::Left => s = network[&s].0.clone(),
-                Direction::Right => s = network[&s].1.clone(),
-            }
-        }
-        steps.push(step);
-    }
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: include_str!("examples/day8.txt"),
+            part1: Some(Answer::Unsigned(2)),
+            part2: None,
+        },
+        Example {
+            input: include_str!("examples/day8b.txt"),
+            part1: Some(Answer::Unsigned(6)),
+            part2: None,
+        },
+        Example {
+            input: include_str!("examples/day8c.txt"),
+            part1: None,
+            part2: Some(Answer::Unsigned(6)),
+        },
+    ];
+    const METADATA: Metadata = Metadata {
+        day: 8,
+        title: "Haunted Wasteland",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse(input.text()) }
+
+    fn part1(input: &Self::Model) -> Result<u64, String> { solution1(input) }
 
-    steps.iter().copied().reduce(lcm).unwrap()
+    fn part2(input: &Self::Model) -> Result<u64, String> { solution2(input) }
 }
@@ -1,36 +1,71 @@
-fn prediction(seq: Vec<i64>) -> i64 {
-    let first = seq.first().unwrap().to_owned();
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::input::Input;
+use crate::solution::{Example, Metadata, Solution};
+
+/// Next value of `seq`, or `None` if it is empty or its differences
+/// overflow.
+fn prediction(seq: Vec<i64>) -> Option<i64> {
+    let first = *seq.first()?;
 
     if seq.iter().all(|&x| x == first) {
-        first
+        Some(first)
     } else {
         let diff: Vec<i64> = seq
             .windows(2)
-            .map(|x| if let &[a, b] = x { b - a } else { 0 })
-            .collect();
-        seq.last().unwrap() + prediction(diff)
+            .map(|x| if let &[a, b] = x { b.checked_sub(a) } else { Some(0) })
+            .collect::<Option<_>>()?;
+        seq.last()?.checked_add(prediction(diff)?)
     }
 }
 
-pub fn solution1(input: Vec<String>) -> i64 {
-    let mut output: Vec<i64> = Vec::new();
+/// Sum of the predictions of `sequences`.
+fn predict(sequences: impl Iterator<Item = Vec<i64>>) -> Result<i64, String> {
+    sequences
+        .map(prediction)
+        .try_fold(0, |sum: i64, prediction| sum.checked_add(prediction?))
+        .ok_or_else(|| "a history is too steep to predict".to_string())
+}
 
-    for line in input {
-        let seq: Vec<i64> = line.split(' ').map(|x| x.parse().unwrap()).collect();
-        output.push(prediction(seq));
-    }
+fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    let mut column = 1;
+    input
+        .split(' ')
+        .map(|x| {
+            let number = x.parse().map_err(|_| {
+                ParseError::new(1, column, input, format!("expected a number, found `{x}`"))
+            });
+            column += x.len() + 1;
+            number
+        })
+        .collect()
+}
+
+pub fn solution1(input: &[Vec<i64>]) -> Result<i64, String> { predict(input.iter().cloned()) }
 
-    output.iter().sum()
+pub fn solution2(input: &[Vec<i64>]) -> Result<i64, String> {
+    predict(input.iter().map(|seq| seq.iter().rev().copied().collect()))
 }
 
-pub fn solution2(input: Vec<String>) -> i64 {
-    let mut output: Vec<i64> = Vec::new();
+pub struct Day9;
 
-    for line in input {
-        let mut seq: Vec<i64> = line.split(' ').map(|x| x.parse().unwrap()).collect();
-        seq.reverse();
-        output.push(prediction(seq));
-    }
+impl Solution for Day9 {
+    type Model = Vec<Vec<i64>>;
+    type Output = Result<i64, String>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: include_str!("examples/day9.txt"),
+        part1: Some(Answer::Signed(114)),
+        part2: Some(Answer::Signed(2)),
+    }];
+    const METADATA: Metadata = Metadata {
+        day: 9,
+        title: "Mirage Maintenance",
+    };
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> { parse_lines(input.lines(), parse) }
+
+    fn part1(input: &Self::Model) -> Result<i64, String> { solution1(input) }
 
-    output.iter().sum()
+    fn part2(input: &Self::Model) -> Result<i64, String> { solution2(input) }
 }
@@ -1,3 +1,5 @@
+use crate::solution::{Metadata, Puzzle, Unimplemented};
+
 pub mod day1;
 pub mod day10;
 pub mod day11;
@@ -10,12 +12,6 @@ pub mod day17;
 pub mod day18;
 pub mod day19;
 pub mod day2;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
 pub mod day3;
 pub mod day4;
 pub mod day5;
@@ -23,3 +19,54 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+
+/// Every day of the calendar, in order.
+pub static DAYS: [&dyn Puzzle; 25] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &Unimplemented(Metadata {
+        day: 20,
+        title: "Pulse Propagation",
+    }),
+    &Unimplemented(Metadata {
+        day: 21,
+        title: "Step Counter",
+    }),
+    &Unimplemented(Metadata {
+        day: 22,
+        title: "Sand Slabs",
+    }),
+    &Unimplemented(Metadata {
+        day: 23,
+        title: "A Long Walk",
+    }),
+    &Unimplemented(Metadata {
+        day: 24,
+        title: "Never Tell Me The Odds",
+    }),
+    &Unimplemented(Metadata {
+        day: 25,
+        title: "Snowverload",
+    }),
+];
+
+pub fn get(day: u8) -> Option<&'static dyn Puzzle> {
+    DAYS.iter().copied().find(|p| p.metadata().day == day)
+}
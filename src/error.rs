@@ -0,0 +1,147 @@
+use std::fmt;
+use std::str::FromStr;
+
+use pest::error::{Error as PestError, LineColLocation};
+use pest::iterators::Pair;
+use pest::RuleType;
+
+/// Error found while parsing a puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number, starting at 1.
+    pub column: usize,
+    /// Content of the offending line.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Move the error down by `lines`, for inputs parsed one line at a time.
+    pub fn offset(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Render the error like a compiler diagnostic, pointing at `path`.
+    pub fn render(&self, path: &str) -> String {
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+        format!(
+            "error: {}\n{pad}--> {path}:{}:{}\n{pad} |\n{number} | {}\n{pad} | {}^",
+            self.message,
+            self.line,
+            self.column,
+            self.text,
+            " ".repeat(self.column.saturating_sub(1)),
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl<R: RuleType> From<PestError<R>> for ParseError {
+    fn from(error: PestError<R>) -> Self {
+        let (line, column) = match error.line_col {
+            LineColLocation::Pos(pos) => pos,
+            LineColLocation::Span(start, _) => start,
+        };
+        ParseError::new(line, column, error.line(), error.variant.message())
+    }
+}
+
+/// Parse the text matched by `pair` as a number, pointing at it when it does
+/// not fit in `T`.
+pub fn parse_number<T, R>(pair: &Pair<R>) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    R: RuleType,
+{
+    pair.as_str().parse().map_err(|e| {
+        let (line, column) = pair.line_col();
+        let text = pair.as_span().start_pos().line_of();
+        ParseError::new(
+            line,
+            column,
+            text.trim_end_matches(['\r', '\n']),
+            format!("invalid number `{}`: {e}", pair.as_str()),
+        )
+    })
+}
+
+/// Parse each line of `input` on its own, keeping track of line numbers.
+pub fn parse_lines<'a, T, F>(
+    input: impl IntoIterator<Item = &'a str>,
+    parse: F,
+) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input
+        .into_iter()
+        .enumerate()
+        .map(|(n, line)| parse(line).map_err(|e| e.offset(n)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day2::Day2;
+    use crate::input::Input;
+    use crate::solution::Solution;
+
+    #[test]
+    fn render_points_at_the_column() {
+        let error = ParseError::new(12, 3, "ab?d", "unexpected `?`");
+        assert_eq!(
+            error.render("day1.txt"),
+            "error: unexpected `?`\n  --> day1.txt:12:3\n   |\n12 | ab?d\n   |   ^"
+        );
+        assert_eq!(error.to_string(), "line 12, column 3: unexpected `?`");
+    }
+
+    #[test]
+    fn lines_are_numbered_from_one() {
+        let error = parse_lines(["1", "2", "x"], |line| {
+            line.parse::<u8>()
+                .map_err(|e| ParseError::new(1, 1, line, e.to_string()))
+        })
+        .unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (3, 1, "x"));
+    }
+
+    #[test]
+    fn grammar_and_number_errors() {
+        let input = Input::from("Game 1: 3 blue\nGame 2: 3 purple\n");
+        let error = Day2::parse(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 11));
+        assert_eq!(error.text, "Game 2: 3 purple");
+
+        let input = Input::from("Game 1: 3 blue\nGame 99999999999: 3 blue\n");
+        let error = Day2::parse(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert!(error.message.starts_with("invalid number `99999999999`"));
+    }
+}
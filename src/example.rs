@@ -0,0 +1,78 @@
+use crate::answer::Answer;
+use crate::calendar::Calendar;
+use crate::input::Input;
+use crate::runner::{self, Report};
+use crate::solution::{Part, Puzzle};
+use crate::verify::{self, Verdict};
+
+/// Result of solving a part on one of the examples of a day.
+pub struct Checked {
+    /// Position of the example in [`Puzzle::examples`], starting at 1.
+    pub example: usize,
+    pub report: Report,
+    pub expected: &'static Answer,
+}
+
+impl Checked {
+    pub fn verdict(&self) -> Verdict { verify::check(&self.report, Some(self.expected)) }
+}
+
+/// Solve the examples of `puzzle`, for every part with an expected answer.
+pub fn check(puzzle: &dyn Puzzle) -> Vec<Checked> {
+    let mut checked = Vec::new();
+
+    for (n, example) in puzzle.examples().iter().enumerate() {
+        let parts: Vec<Part> = Part::ALL
+            .into_iter()
+            .filter(|&part| example.expected(part).is_some())
+            .collect();
+        let input = Input::from(example.input);
+        for report in runner::run(puzzle, &parts, &input) {
+            let expected = example.expected(report.part).unwrap();
+            checked.push(Checked {
+                example: n + 1,
+                report,
+                expected,
+            });
+        }
+    }
+
+    checked
+}
+
+/// Solve the examples of `days`, or of every day of `calendar` with examples
+/// when there is none.
+pub fn check_days(calendar: &Calendar, days: &[u8]) -> Vec<Checked> {
+    let puzzles: Vec<&dyn Puzzle> = if days.is_empty() {
+        calendar.days.to_vec()
+    } else {
+        days.iter().filter_map(|&day| calendar.get(day)).collect()
+    };
+
+    puzzles.into_iter().flat_map(check).collect()
+}
+
+/// Print the result of every check and return whether none of them failed.
+pub fn print_checks(checks: &[Checked]) -> bool {
+    let mut passed = true;
+
+    println!(
+        "{:>4} {:>7} {:>4}  {:<8} Details",
+        "Day", "Example", "Part", "Status"
+    );
+    for check in checks {
+        let verdict = check.verdict();
+        passed &= !verdict.is_failure();
+
+        println!(
+            "{:>4} {:>7} {:>4}  {:<8} {}",
+            check.report.day,
+            check.example,
+            check.report.part.number(),
+            verdict.status(),
+            verify::details(&check.report, Some(check.expected))
+        );
+    }
+
+    passed
+}
@@ -0,0 +1,66 @@
+//! Helpers shared by the unit and integration tests, the latter getting them
+//! through the `fixture` feature.
+
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::{env, fs};
+
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Metadata, Solution};
+
+/// Empty directory unique to a test, removed once dropped.
+pub struct Scratch(PathBuf);
+
+impl Scratch {
+    pub fn new(name: &str) -> Self {
+        let dir = env::temp_dir().join(format!("advent-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        Scratch(dir)
+    }
+}
+
+impl Deref for Scratch {
+    type Target = Path;
+
+    fn deref(&self) -> &Path { &self.0 }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) { let _ = fs::remove_dir_all(&self.0); }
+}
+
+/// Counts the parsings of each input file, and answers with the length of its
+/// input.
+pub struct Counted;
+
+static PARSES: Mutex<BTreeMap<PathBuf, usize>> = Mutex::new(BTreeMap::new());
+
+impl Counted {
+    /// Number of times the input read from `path` was parsed.
+    pub fn parses(path: &Path) -> usize { PARSES.lock().unwrap().get(path).copied().unwrap_or(0) }
+}
+
+impl Solution for Counted {
+    type Model = usize;
+    type Output = usize;
+
+    const METADATA: Metadata = Metadata {
+        day: 1,
+        title: "Counted",
+    };
+
+    fn parse(input: &Input) -> Result<usize, ParseError> {
+        if let Some(path) = input.path() {
+            *PARSES.lock().unwrap().entry(path.into()).or_default() += 1;
+        }
+        Ok(input.text().len())
+    }
+
+    fn part1(input: &usize) -> usize { *input }
+
+    fn part2(input: &usize) -> usize { input * 2 }
+}
@@ -0,0 +1,224 @@
+use std::ops::Not;
+use std::str::FromStr;
+
+/// One of the four orthogonal directions, in clockwise order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_right(self) -> Self { Direction::ALL[(self as usize + 1) % 4] }
+
+    pub fn turn_left(self) -> Self { Direction::ALL[(self as usize + 3) % 4] }
+
+    pub fn reverse(self) -> Self { Direction::ALL[(self as usize + 2) % 4] }
+
+    pub fn is_vertical(self) -> bool { matches!(self, Direction::Up | Direction::Down) }
+
+    /// Change of row and column of a single step.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+        }
+    }
+}
+
+impl Not for Direction {
+    type Output = Direction;
+
+    fn not(self) -> Self::Output { self.reverse() }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    /// Read a direction from its initial, or from the digit of the
+    /// hexadecimal encoding where `0` is right, `1` down, `2` left and `3` up.
+    fn try_from(value: char) -> Result<Self, char> {
+        match value {
+            'U' | '3' => Ok(Direction::Up),
+            'R' | '0' => Ok(Direction::Right),
+            'D' | '1' => Ok(Direction::Down),
+            'L' | '2' => Ok(Direction::Left),
+            _ => Err(value),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into().map_err(|c| format!("unknown direction `{c}`")),
+            _ => Err(format!("unknown direction `{s}`")),
+        }
+    }
+}
+
+/// One of the eight directions, diagonals included, in clockwise order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// Turn by an eighth of a full turn.
+    pub fn turn_right(self) -> Self { Direction8::ALL[(self as usize + 1) % 8] }
+
+    /// Turn back by an eighth of a full turn.
+    pub fn turn_left(self) -> Self { Direction8::ALL[(self as usize + 7) % 8] }
+
+    pub fn reverse(self) -> Self { Direction8::ALL[(self as usize + 4) % 8] }
+
+    /// Change of row and column of a single step.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction8::Up => (-1, 0),
+            Direction8::UpRight => (-1, 1),
+            Direction8::Right => (0, 1),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (1, 0),
+            Direction8::DownLeft => (1, -1),
+            Direction8::Left => (0, -1),
+            Direction8::UpLeft => (-1, -1),
+        }
+    }
+}
+
+impl Not for Direction8 {
+    type Output = Direction8;
+
+    fn not(self) -> Self::Output { self.reverse() }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self { Direction8::ALL[value as usize * 2] }
+}
+
+/// Position on an unbounded plane.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub row: i64,
+    pub column: i64,
+}
+
+impl Point {
+    pub fn new(row: i64, column: i64) -> Self { Point { row, column } }
+
+    /// Move `distance` steps towards `direction`, or `None` on overflow.
+    pub fn step(self, direction: Direction, distance: i64) -> Option<Self> {
+        let (rows, columns) = direction.offset();
+        Some(Point {
+            row: self.row.checked_add((rows as i64).checked_mul(distance)?)?,
+            column: self
+                .column
+                .checked_add((columns as i64).checked_mul(distance)?)?,
+        })
+    }
+}
+
+/// Position in a grid, which cannot go above the first row or left of the
+/// first column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coord {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Coord {
+    pub fn new(row: usize, column: usize) -> Self { Coord { row, column } }
+
+    /// Move by `offset` rows and columns, or `None` before the first ones.
+    pub fn offset(self, (rows, columns): (isize, isize)) -> Option<Self> {
+        Some(Coord {
+            row: self.row.checked_add_signed(rows)?,
+            column: self.column.checked_add_signed(columns)?,
+        })
+    }
+
+    pub fn step(self, direction: Direction) -> Option<Self> { self.offset(direction.offset()) }
+
+    pub fn step8(self, direction: Direction8) -> Option<Self> { self.offset(direction.offset()) }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((row, column): (usize, usize)) -> Self { Coord { row, column } }
+}
+
+impl From<Coord> for (usize, usize) {
+    fn from(value: Coord) -> Self { (value.row, value.column) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), !direction);
+            let (rows, columns) = direction.offset();
+            assert_eq!((!direction).offset(), (-rows, -columns));
+            assert_eq!(Direction8::from(direction).offset(), direction.offset());
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("U".parse(), Ok(Direction::Up));
+        assert_eq!("0".parse(), Ok(Direction::Right));
+        assert_eq!(Direction::try_from('1'), Ok(Direction::Down));
+        assert!("UL".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn stepping() {
+        assert_eq!(Coord::new(0, 3).step(Direction::Up), None);
+        assert_eq!(
+            Coord::new(0, 3).step8(Direction8::DownLeft),
+            Some(Coord::new(1, 2))
+        );
+        assert_eq!(
+            Point::default().step(Direction::Left, 5),
+            Some(Point::new(0, -5))
+        );
+        assert_eq!(Point::new(0, i64::MIN).step(Direction::Left, 1), None);
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{parse_lines, ParseError};
+use crate::geometry::{Coord, Direction};
+
+/// A rectangular grid, stored row by row in a single vector.
+///
+/// Cells are addressed by [`Coord`], starting from the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Build a grid out of `rows`, returning the index of the first row whose
+    /// length differs from the one before it.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, usize> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        if let Some(n) = rows.iter().position(|row| row.len() != width) {
+            return Err(n);
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parse every character of the lines of `input` with `parse`, which
+    /// explains why a character is rejected.
+    pub fn parse_with<F>(input: &str, parse: F) -> Result<Self, ParseError>
+    where
+        F: Fn(char) -> Result<T, String>,
+    {
+        let rows = parse_lines(input.lines(), |line| {
+            line.chars()
+                .enumerate()
+                .map(|(n, c)| parse(c).map_err(|e| ParseError::new(1, n + 1, line, e)))
+                .collect()
+        })?;
+
+        Grid::from_rows(rows).map_err(|n| {
+            let line = input.lines().nth(n).unwrap_or_default();
+            let width = input
+                .lines()
+                .next()
+                .map_or(0, |first| first.chars().count());
+            let found = line.chars().count();
+            ParseError::new(
+                n + 1,
+                width.min(found) + 1,
+                line,
+                format!("expected {width} cells, found {found}"),
+            )
+        })
+    }
+
+    pub fn width(&self) -> usize { self.width }
+
+    pub fn height(&self) -> usize { self.height }
+
+    pub fn get(&self, Coord { row, column }: Coord) -> Option<&T> {
+        if row < self.height && column < self.width {
+            self.cells.get(row * self.width + column)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, Coord { row, column }: Coord) -> Option<&mut T> {
+        if row < self.height && column < self.width {
+            self.cells.get_mut(row * self.width + column)
+        } else {
+            None
+        }
+    }
+
+    /// Position `offset` rows and columns away from `coord`, if it is inside
+    /// the grid.
+    pub fn offset(&self, coord: Coord, offset: (isize, isize)) -> Option<Coord> {
+        coord
+            .offset(offset)
+            .filter(|c| c.row < self.height && c.column < self.width)
+    }
+
+    /// Positions of the cells above, right, below and left of `coord` that
+    /// are inside the grid.
+    pub fn neighbours(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(coord, direction))
+    }
+
+    /// Neighbour of `coord` towards `direction`, if it is inside the grid.
+    pub fn step(&self, coord: Coord, direction: Direction) -> Option<Coord> {
+        self.offset(coord, direction.offset())
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "row {row} is outside of the grid");
+        &self.cells[row * self.width..][..self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.height, "row {row} is outside of the grid");
+        &mut self.cells[row * self.width..][..self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> { self.cells.chunks(self.width.max(1)) }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.cells.chunks_mut(self.width.max(1))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            column < self.width,
+            "column {column} is outside of the grid"
+        );
+        self.cells.iter().skip(column).step_by(self.width.max(1))
+    }
+
+    pub fn column_mut(&mut self, column: usize) -> impl Iterator<Item = &mut T> {
+        assert!(
+            column < self.width,
+            "column {column} is outside of the grid"
+        );
+        let width = self.width.max(1);
+        self.cells.iter_mut().skip(column).step_by(width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(n, cell)| (Coord::new(n / width, n % width), cell))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Build a `width` by `height` grid, taking each cell from `cell`.
+    fn build<F>(width: usize, height: usize, cell: F) -> Self
+    where
+        F: Fn(usize, usize) -> T,
+    {
+        Grid {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|row| (0..width).map(move |column| (row, column)))
+                .map(|(row, column)| cell(row, column))
+                .collect(),
+        }
+    }
+
+    /// Swap the rows and the columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::build(self.height, self.width, |row, column| {
+            self[Coord::new(column, row)].clone()
+        })
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::build(self.height, self.width, |row, column| {
+            self[Coord::new(self.height - 1 - column, row)].clone()
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::build(self.height, self.width, |row, column| {
+            self[Coord::new(column, self.width - 1 - row)].clone()
+        })
+    }
+}
+
+impl<T: TryFrom<char>> Grid<T> {
+    /// Parse every character of the lines of `input` into a cell.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Grid::parse_with(input, |c| {
+            T::try_from(c).map_err(|_| format!("unexpected `{c}`"))
+        })
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is outside of the grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, row) in self.rows().enumerate() {
+            if n > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid<char> { Grid::parse_with(text, Ok).unwrap() }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let error = Grid::parse_with("ab\nc", Ok::<char, String>).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn views() {
+        let grid = grid("abc\ndef");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coord::new(1, 0)], 'd');
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.get(Coord::new(2, 0)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside of the grid")]
+    fn columns_stay_inside() { grid("abc\ndef").column(3).count(); }
+
+    #[test]
+    #[should_panic(expected = "row 2 is outside of the grid")]
+    fn rows_stay_inside() { grid("abc\ndef").row(2); }
+
+    #[test]
+    fn stepping_stays_inside() {
+        let grid = grid("abc\ndef");
+        assert_eq!(grid.offset(Coord::new(0, 0), (0, -1)), None);
+        assert_eq!(
+            grid.offset(Coord::new(0, 2), (1, -2)),
+            Some(Coord::new(1, 0))
+        );
+        assert_eq!(grid.step(Coord::new(1, 2), Direction::Right), None);
+        assert_eq!(
+            grid.neighbours(Coord::new(0, 0)).collect::<Vec<_>>(),
+            [Coord::new(0, 1), Coord::new(1, 0)]
+        );
+    }
+
+    #[test]
+    fn transformations() {
+        let grid = grid("abc\ndef");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
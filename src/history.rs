@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fmt, fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::client::{self, Client, Submission, Verdict};
+use crate::solution::Part;
+
+/// Name of the history file, in the directory of the inputs of the event.
+pub const FILE: &str = "submissions.json";
+
+/// An answer given to the server, and how it was judged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    /// Seconds to wait before the next submission, as told by the server.
+    pub wait: Option<u64>,
+}
+
+/// Why an answer should not be submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved with this answer.
+    Solved(Answer),
+    /// The answer was already rejected.
+    Rejected(Verdict),
+    /// The server asked to wait this much longer.
+    Cooldown(Duration),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "already solved with {answer}"),
+            Refusal::Rejected(verdict) => write!(f, "already submitted, the answer was {verdict}"),
+            Refusal::Cooldown(wait) => {
+                write!(f, "wait {}s before submitting again", wait.as_secs())
+            },
+        }
+    }
+}
+
+/// Why an answer was not submitted.
+#[derive(Debug)]
+pub enum Unsent {
+    Refused(Refusal),
+    Failed(client::Error),
+}
+
+impl fmt::Display for Unsent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unsent::Refused(refusal) => write!(f, "not submitting, {refusal}"),
+            Unsent::Failed(e) => write!(f, "could not submit the answer: {e}"),
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Every answer submitted for an event, stored as JSON.
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Read the history at `path`, which is empty if the file does not exist.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let attempts = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(History { path, attempts })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.attempts)?)
+    }
+
+    pub fn attempts(&self) -> &[Attempt] { &self.attempts }
+
+    /// Check that submitting `answer` to `part` of `day` is worth a request.
+    pub fn check(&self, day: u8, part: u8, answer: &Answer) -> Result<(), Refusal> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|a| a.day == day && a.part == part);
+        for attempt in attempts {
+            if attempt.verdict == Verdict::Correct {
+                return Err(Refusal::Solved(attempt.answer.clone()));
+            }
+            if attempt.verdict.is_wrong() && attempt.answer == *answer {
+                return Err(Refusal::Rejected(attempt.verdict));
+            }
+        }
+
+        // The cooldown applies to every puzzle, not only to the one answered.
+        let until = self
+            .attempts
+            .iter()
+            .filter_map(|a| Some(a.time + a.wait?))
+            .max()
+            .unwrap_or(0);
+        match until.checked_sub(now()) {
+            Some(wait) if wait > 0 => Err(Refusal::Cooldown(Duration::from_secs(wait))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Submit `answer` to `part` of `day` through `client`, unless
+    /// [`History::check`] refuses it, and record how it was judged.
+    pub fn submit(
+        &mut self,
+        client: &Client,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: Answer,
+    ) -> Result<Submission, Unsent> {
+        self.check(day, part.number(), &answer)
+            .map_err(Unsent::Refused)?;
+        let submission = client
+            .submit(year, day, part, &answer)
+            .map_err(Unsent::Failed)?;
+        self.record(day, part.number(), answer, submission);
+        Ok(submission)
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, answer: Answer, submission: Submission) {
+        self.attempts.push(Attempt {
+            day,
+            part,
+            answer,
+            verdict: submission.verdict,
+            time: now(),
+            wait: submission.wait.map(|w| w.as_secs()),
+        });
+    }
+}
@@ -0,0 +1,185 @@
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Puzzle input, kept as the whole text it was read from.
+///
+/// Days can either parse the text at once, or go through its lines without
+/// copying them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Input {
+    text: String,
+    path: Option<PathBuf>,
+}
+
+impl Input {
+    /// Input made of `text`, whose Windows line endings become `\n`.
+    fn new(text: String, path: Option<PathBuf>) -> Self {
+        Input {
+            text: normalise(text),
+            path,
+        }
+    }
+
+    /// Read the input from `path`, or from the standard input when it is `-`.
+    pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut text = String::new();
+        if path == Path::new("-") {
+            io::stdin().lock().read_to_string(&mut text)?;
+        } else {
+            File::open(path)?.read_to_string(&mut text)?;
+        }
+        Ok(Input::new(text, Some(path.to_path_buf())))
+    }
+
+    /// Lines of the input, without their line endings.
+    pub fn lines(&self) -> std::str::Lines<'_> { self.text.lines() }
+
+    /// File the input was read from, `-` standing for the standard input.
+    pub fn path(&self) -> Option<&Path> { self.path.as_deref() }
+
+    /// Name of the input in diagnostics, from [`name`].
+    pub fn name(&self) -> String { self.path().map_or_else(|| "<input>".to_string(), name) }
+
+    /// Whole input, without the line ending of its last line.
+    pub fn text(&self) -> &str { self.text.strip_suffix('\n').unwrap_or(&self.text) }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self { Input::new(text, None) }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self { Input::new(text.to_string(), None) }
+}
+
+impl From<Vec<String>> for Input {
+    fn from(lines: Vec<String>) -> Self {
+        Input::new(lines.into_iter().map(|line| line + "\n").collect(), None)
+    }
+}
+
+/// Drop the `\r` of every `\r\n` in `text`, moving the bytes after it
+/// within the same buffer rather than copying the whole text.
+fn normalise(text: String) -> String {
+    if !text.contains('\r') {
+        return text;
+    }
+
+    let mut bytes = text.into_bytes();
+    let mut kept = 0;
+    for n in 0..bytes.len() {
+        if bytes[n] != b'\r' || bytes.get(n + 1) != Some(&b'\n') {
+            bytes[kept] = bytes[n];
+            kept += 1;
+        }
+    }
+    bytes.truncate(kept);
+    // Only ASCII bytes were removed, so the text is still valid UTF-8.
+    String::from_utf8(bytes).expect("removing `\r` keeps the text valid")
+}
+
+/// Name of the input read from `path` in diagnostics, `-` standing for the
+/// standard input.
+pub fn name(path: &Path) -> String {
+    if path == Path::new("-") {
+        "<stdin>".to_string()
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// Whether the file name of `path` is that of an input of `day`, such as
+/// `day5.txt` or `day5b.txt` but not `day15.txt`.
+fn is_input_of(path: &Path, day: u8) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy());
+    name.as_deref()
+        .and_then(|name| name.strip_prefix(&format!("day{day}")))
+        .and_then(|rest| rest.strip_suffix(".txt"))
+        .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Files selected by `path` when it is a directory or a glob pattern, in
+/// order, or `None` if it names a single input.
+///
+/// A directory only contributes the inputs of `day`, while a pattern selects
+/// exactly the files it matches.
+pub fn batch(path: &Path, day: u8) -> Option<Result<Vec<PathBuf>, String>> {
+    let files = if path.is_dir() {
+        fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| Some(e.ok()?.path()))
+                    .filter(|p| is_input_of(p, day))
+                    .collect()
+            })
+            .map_err(|e| format!("could not read {}: {e}", path.display()))
+    } else if path.to_string_lossy().contains(['*', '?', '[']) {
+        glob::glob(&path.to_string_lossy())
+            .map(|paths| paths.filter_map(Result::ok).collect())
+            .map_err(|e| format!("invalid pattern {}: {e}", path.display()))
+    } else {
+        return None;
+    };
+
+    Some(files.map(|files: Vec<PathBuf>| {
+        let mut files: Vec<PathBuf> = files.into_iter().filter(|p| p.is_file()).collect();
+        files.sort();
+        files
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::Scratch;
+
+    #[test]
+    fn line_endings() {
+        let input = Input::from("a\r\nb\r\n\r\n");
+        assert_eq!(input.text(), "a\nb\n");
+        assert_eq!(Input::from("a\rb\r\r\n").text(), "a\rb\r");
+        assert_eq!(input.lines().collect::<Vec<_>>(), ["a", "b", ""]);
+        assert_eq!(
+            Input::from(vec!["a".to_string(), "b".to_string()]),
+            Input::from("a\nb\n")
+        );
+        assert_eq!(Input::from("").lines().count(), 0);
+    }
+
+    #[test]
+    fn batch_selects_the_inputs_of_the_day() {
+        let dir = Scratch::new("batch");
+        fs::create_dir_all(dir.join("day1c.txt")).unwrap();
+        for name in [
+            "day1.txt",
+            "day1b.txt",
+            "day10.txt",
+            "day2.txt",
+            "answers.toml",
+        ] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        let files = batch(&dir, 1).unwrap().unwrap();
+        assert_eq!(files, [dir.join("day1.txt"), dir.join("day1b.txt")]);
+
+        let pattern = dir.join("day1*.txt");
+        let files = batch(&pattern, 1).unwrap().unwrap();
+        assert_eq!(
+            files,
+            [
+                dir.join("day1.txt"),
+                dir.join("day10.txt"),
+                dir.join("day1b.txt")
+            ]
+        );
+
+        assert!(batch(&dir.join("day2.txt"), 2).is_none());
+        assert!(batch(&dir.join("missing/day*.txt"), 2)
+            .unwrap()
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+//! Solutions to the puzzles of Advent of Code 2023.
+//!
+//! Every day lives in [`days`], exposing its parsed model and a type
+//! implementing [`Solution`]. The [`days::DAYS`] registry gives access to all
+//! of them through the object safe [`Puzzle`] trait.
+
+pub mod answer;
+pub mod bench;
+pub mod cache;
+pub mod calendar;
+pub mod cancel;
+pub mod client;
+pub mod cycle;
+pub mod days;
+pub mod error;
+pub mod example;
+#[cfg(any(test, feature = "fixture"))]
+pub mod fixture;
+pub mod geometry;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod output;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod verify;
+pub mod watch;
+
+pub use answer::Answer;
+pub use error::ParseError;
+pub use input::Input;
+pub use solution::{Metadata, Part, Puzzle, Solution};
@@ -1,64 +1,679 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, io};
 
-use clap::{Parser, Subcommand, ValueEnum};
-use seq_macro::seq;
+use advent::bench::{self, Baseline, Failure};
+use advent::cache::{self, Cache};
+use advent::calendar::{self, Calendar};
+use advent::client::{self, Client, Error, Fetched, Verdict};
+use advent::history::{self, History};
+use advent::output::{self, Format};
+use advent::runner::{self, Outcome, Report, Task};
+use advent::solution::Part;
+use advent::verify::{self, Answers};
+use advent::{example, input, scaffold, watch};
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 
-mod days;
-use days::*;
+/// Event selected by the year the arguments start with, whose days are
+/// accepted by [`parse_day`].
+static CALENDAR: OnceLock<&'static Calendar> = OnceLock::new();
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+fn parse_day(value: &str) -> Result<u8, String> {
+    CALENDAR
+        .get()
+        .copied()
+        .unwrap_or_else(calendar::latest)
+        .parse_day(value)
+}
+
+fn parse_part(value: &str) -> Result<Part, String> {
+    match value.strip_prefix("part").unwrap_or(value) {
+        "1" => Ok(Part::Part1),
+        "2" => Ok(Part::Part2),
+        _ => Err("expected part1 or part2".to_string()),
+    }
+}
+
+/// Parse a duration such as `500ms`, `10s` or `2m`, in seconds by default.
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let (number, scale) = if let Some(number) = value.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = value.strip_suffix('s') {
+        (number, 1.0)
+    } else if let Some(number) = value.strip_suffix('m') {
+        (number, 60.0)
+    } else {
+        (value, 1.0)
+    };
+
+    match number.parse::<f64>() {
+        Ok(number) if number > 0.0 => {
+            Duration::try_from_secs_f64(number * scale).map_err(|e| e.to_string())
+        },
+        _ => Err("expected a positive duration such as 500ms, 10s or 2m".to_string()),
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Selection {
+    Part1,
+    Part2,
+    /// Parse the input once and solve both parts
+    Both,
+}
+
+impl Selection {
+    fn parts(self) -> &'static [Part] {
+        match self {
+            Selection::Part1 => &[Part::Part1],
+            Selection::Part2 => &[Part::Part2],
+            Selection::Both => &Part::ALL,
+        }
+    }
 }
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 struct Cli {
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
+    #[command(flatten)]
+    run: Option<RunArgs>,
+    #[command(flatten)]
+    output: OutputArgs,
+    /// Run again whenever the input, or a sibling `dayN*` file, changes
+    #[arg(long, conflicts_with_all = ["format", "timeout"])]
+    watch: bool,
 }
 
-#[derive(Clone, ValueEnum)]
-enum Part {
-    Part1,
-    Part2,
+/// Budget of the parsing and of each part.
+#[derive(Args)]
+struct TimeoutArgs {
+    /// Give up on the parsing or on a part once it has run for this long, e.g.
+    /// `500ms` or `10s`, and carry on with the others
+    ///
+    /// Only the days whose long loops check for cancellation, namely days 8,
+    /// 10, 12, 14, 16 and 17, stop at once. Any other day keeps running in the
+    /// background until it is done, while the others carry on.
+    #[arg(long, value_name = "TIME", value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+}
+
+/// How to solve the parts and print their results.
+#[derive(Args)]
+struct OutputArgs {
+    /// Format of the results
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+    #[command(flatten)]
+    limit: TimeoutArgs,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[arg(value_name = "DAY", value_parser = parse_day)]
+    day: u8,
+    #[arg(value_enum)]
+    part: Selection,
+    /// Puzzle input, `-` for stdin [default: inputs/YEAR/dayN.txt]
+    ///
+    /// A directory runs the inputs of the day it holds, such as `dayN.txt` and
+    /// `dayNb.txt`, and a glob pattern every file it matches, in parallel.
+    #[arg(short, long, value_name = "FILE")]
+    input: Option<PathBuf>,
+}
+
+/// Where to find the inputs of several days, and how many to solve at once.
+#[derive(Args)]
+struct DaysArgs {
+    /// Directory holding the inputs, named `dayN.txt` [default: inputs/YEAR]
+    #[arg(long, value_name = "DIR")]
+    inputs: Option<PathBuf>,
+    /// Number of parts solved at the same time
+    #[arg(short, long, value_name = "N", default_value = "1")]
+    jobs: NonZeroUsize,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    target: RunArgs,
+    /// Number of timed runs
+    #[arg(short = 'n', long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+    /// Number of untimed runs done beforehand
+    #[arg(long, default_value_t = 3)]
+    warmup: u32,
+    /// Save the timings as a JSON baseline
+    #[arg(long, value_name = "FILE")]
+    save: Option<PathBuf>,
+    /// Compare the timings against a saved baseline
+    #[arg(long, value_name = "FILE")]
+    compare: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Solve both parts of every day and print a table of the results
+    All {
+        #[command(flatten)]
+        solve: DaysArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// Solve every part again instead of reusing the cached answers
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Check the answers of the selected days against a stored answers file
+    Verify {
+        /// Days to check, defaults to every day in the answers file
+        #[arg(value_name = "DAY", value_parser = parse_day)]
+        days: Vec<u8>,
+        #[command(flatten)]
+        solve: DaysArgs,
+        #[command(flatten)]
+        limit: TimeoutArgs,
+        /// TOML file with the expected answers [default:
+        /// inputs/YEAR/answers.toml]
+        #[arg(long, value_name = "FILE")]
+        answers: Option<PathBuf>,
+    },
+    /// Check the selected days against the examples of the puzzle text
+    Example {
+        /// Days to check, defaults to every day with examples
+        #[arg(value_name = "DAY", value_parser = parse_day)]
+        days: Vec<u8>,
+    },
+    /// Generate the module of a new day and register it
+    New {
+        #[arg(value_name = "DAY", value_parser = parse_day)]
+        day: u8,
+        /// Also generate a pest grammar and parser
+        #[arg(long)]
+        pest: bool,
+        /// Root of the crate to generate the day in
+        #[arg(long, value_name = "DIR", default_value = env!("CARGO_MANIFEST_DIR"))]
+        root: PathBuf,
+    },
+    /// Time the parsing and solving of a day over many runs
+    Bench(BenchArgs),
+    /// Download the inputs of the selected days, skipping the existing ones
+    Fetch {
+        #[arg(value_name = "DAY", value_parser = parse_day, required = true)]
+        days: Vec<u8>,
+        /// TOML file with the `session` token, `base_url` and request
+        /// `interval` [env: AOC_SESSION overrides the session]
+        #[arg(long, value_name = "FILE", default_value = client::CONFIG)]
+        config: PathBuf,
+    },
+    /// Solve a part and submit its answer, unless it is known to be wrong
+    Submit {
+        #[arg(value_name = "DAY", value_parser = parse_day)]
+        day: u8,
+        #[arg(value_name = "PART", value_parser = parse_part)]
+        part: Part,
+        /// Puzzle input, `-` for stdin [default: inputs/YEAR/dayN.txt]
+        #[arg(short, long, value_name = "FILE")]
+        input: Option<PathBuf>,
+        /// TOML file with the `session` token, `base_url` and request
+        /// `interval` [env: AOC_SESSION overrides the session]
+        #[arg(long, value_name = "FILE", default_value = client::CONFIG)]
+        config: PathBuf,
+    },
+    /// Manage the answers cached by `all`
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Print every cached answer
+    List,
+    /// Forget every cached answer
+    Clear,
+}
+
+/// Write `reports` to stdout as a single document in `format`.
+fn print_reports(format: Format, reports: &[Report]) {
+    let result = match format {
+        Format::Text => {
+            runner::print_table(reports);
+            Ok(())
+        },
+        Format::Json => output::write_json(io::stdout().lock(), reports),
+        Format::Csv => output::write_csv(io::stdout().lock(), reports),
+    };
+    if let Err(e) = result {
+        eprintln!("could not write the results: {e}");
+    }
+}
+
+fn exit_code(success: bool) -> ExitCode {
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn batch(
+    calendar: &Calendar,
+    args: RunArgs,
+    files: Vec<PathBuf>,
+    format: Format,
+    timeout: Option<Duration>,
+) -> ExitCode {
+    let puzzle = match calendar.solved(args.day) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    runner::silence_panics();
+    let reports = runner::run_files(puzzle, args.part.parts(), &files, timeout);
+    if format == Format::Text {
+        runner::print_file_table(&reports);
+    } else {
+        print_reports(format, &reports);
+    }
+
+    exit_code(!runner::failed(&reports))
+}
+
+fn run(calendar: &Calendar, args: RunArgs, OutputArgs { format, limit }: OutputArgs) -> ExitCode {
+    let timeout = limit.timeout;
+    match args
+        .input
+        .as_deref()
+        .and_then(|path| input::batch(path, args.day))
+    {
+        Some(Ok(files)) if files.is_empty() => {
+            eprintln!("no input matches {}", args.input.unwrap().display());
+            return ExitCode::FAILURE;
+        },
+        Some(Ok(files)) => return batch(calendar, args, files, format, timeout),
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        },
+        None => (),
+    }
+
+    let (puzzle, input) = match calendar.open(args.day, args.input.as_deref()) {
+        Ok(opened) => opened,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    runner::silence_panics();
+    let name = input.name();
+    let reports = runner::run_within(puzzle, args.part.parts(), input, timeout);
+    if format != Format::Text {
+        print_reports(format, &reports);
+        return exit_code(!runner::failed(&reports));
+    }
+
+    // Every part holds the same error when the input does not parse.
+    if let Some(Outcome::Invalid(e)) = reports.first().map(|r| &r.outcome) {
+        eprintln!("{}", e.render(&name));
+        return ExitCode::FAILURE;
+    }
+
+    for report in &reports {
+        let part = report.part.number();
+        match &report.outcome {
+            Outcome::Solved(answer) => println!("{answer}"),
+            Outcome::Skipped(reason) => eprintln!("day{} part{part} skipped: {reason}", args.day),
+            Outcome::Invalid(e) => eprintln!("day{} part{part} failed: {e}", args.day),
+            Outcome::Failed(reason) => eprintln!("day{} part{part} failed: {reason}", args.day),
+            Outcome::TimedOut => eprintln!(
+                "day{} part{part} timed out after {:.2?}",
+                args.day, report.elapsed
+            ),
+        }
+    }
+
+    exit_code(!runner::failed(&reports))
+}
+
+fn watch(calendar: &Calendar, args: RunArgs) -> ExitCode {
+    let path = args.input.unwrap_or_else(|| calendar.input(args.day));
+    if path == Path::new("-") {
+        eprintln!("cannot watch the standard input");
+        return ExitCode::FAILURE;
+    }
+    let puzzle = match calendar.open(args.day, Some(&path)) {
+        Ok((puzzle, _)) => puzzle,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    runner::silence_panics();
+    watch::run(puzzle, args.part.parts(), &path)
+}
+
+fn bench(calendar: &Calendar, args: BenchArgs) -> ExitCode {
+    let BenchArgs {
+        target,
+        runs,
+        warmup,
+        save,
+        compare,
+    } = args;
+    let (puzzle, input) = match calendar.open(target.day, target.input.as_deref()) {
+        Ok(opened) => opened,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let baseline = match compare.as_ref().map(Baseline::load).transpose() {
+        Ok(Some(baseline)) if baseline.day != target.day => {
+            eprintln!(
+                "{} holds timings of day{}, not day{}",
+                compare.unwrap().display(),
+                baseline.day,
+                target.day
+            );
+            return ExitCode::FAILURE;
+        },
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("could not read {}: {e}", compare.unwrap().display());
+            return ExitCode::FAILURE;
+        },
+    };
+
+    runner::silence_panics();
+    let current = match bench::bench(puzzle, target.part.parts(), &input, warmup, runs) {
+        Ok(current) => current,
+        Err(Failure::Parse(e)) => {
+            eprintln!("{}", e.render(&input.name()));
+            return ExitCode::FAILURE;
+        },
+        Err(Failure::Failed { step, message }) => {
+            eprintln!("day{} {step} failed: {message}", target.day);
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let metadata = puzzle.metadata();
+    println!(
+        "day{} ({}), {runs} runs after {warmup} warm-up",
+        metadata.day, metadata.title
+    );
+    bench::print_stats(&current, baseline.as_ref());
+
+    if let Some(save) = save {
+        if let Err(e) = current.save(&save) {
+            eprintln!("could not write {}: {e}", save.display());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
 }
 
-seq!( N in 1..=25 {
-    #[derive(Subcommand)]
-    enum Commands {
-        #(
-            Day~N{
-                #[arg(value_enum)]
-                part: Part,
-                #[arg(short, long, value_name = "FILE")]
-                input: PathBuf,
+fn all(
+    calendar: &Calendar,
+    DaysArgs { inputs, jobs }: DaysArgs,
+    OutputArgs { format, limit }: OutputArgs,
+    no_cache: bool,
+) -> ExitCode {
+    let timeout = limit.timeout;
+    let inputs = inputs.unwrap_or_else(|| calendar.inputs());
+    let tasks = Task::days(calendar.days, &inputs);
+
+    runner::silence_panics();
+    let reports = cache::run_tasks(calendar.year, &tasks, jobs.get(), timeout, no_cache);
+    print_reports(format, &reports);
+
+    exit_code(!runner::failed(&reports))
+}
+
+fn verify(
+    calendar: &Calendar,
+    days: Vec<u8>,
+    DaysArgs { inputs, jobs }: DaysArgs,
+    answers: Option<PathBuf>,
+    timeout: Option<Duration>,
+) -> ExitCode {
+    let inputs = inputs.unwrap_or_else(|| calendar.inputs());
+    let answers = answers.unwrap_or_else(|| inputs.join("answers.toml"));
+    let answers = match Answers::load(&answers, calendar) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("could not read {}: {e}", answers.display());
+            return ExitCode::FAILURE;
+        },
+    };
+
+    runner::silence_panics();
+    match verify::solve(calendar, &days, &answers, &inputs, jobs.get(), timeout) {
+        Ok(reports) => exit_code(verify::print_verdicts(&reports, &answers)),
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn example(calendar: &Calendar, days: Vec<u8>) -> ExitCode {
+    runner::silence_panics();
+    let checks = example::check_days(calendar, &days);
+
+    if checks.is_empty() {
+        eprintln!("no examples to check");
+        return ExitCode::FAILURE;
+    }
+
+    exit_code(example::print_checks(&checks))
+}
+
+fn new(calendar: &Calendar, day: u8, pest: bool, root: PathBuf) -> ExitCode {
+    let puzzle = calendar.get(day).unwrap();
+    if puzzle.implemented() {
+        eprintln!("day{day} already exists");
+        return ExitCode::FAILURE;
+    }
+
+    match scaffold::scaffold(&root, calendar.module, day, puzzle.metadata().title, pest) {
+        Ok(created) => {
+            for path in created {
+                println!("created {}", path.display());
+            }
+            ExitCode::SUCCESS
+        },
+        Err(e) => {
+            eprintln!("could not generate day{day}: {e}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn fetch(calendar: &Calendar, days: Vec<u8>, config: PathBuf) -> ExitCode {
+    let client = match Client::load(&config) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let mut code = ExitCode::SUCCESS;
+    for day in days {
+        let path = calendar.input(day);
+        match client.fetch(calendar.year, day, &path) {
+            Ok(Fetched::Downloaded) => println!("day{day}: saved {}", path.display()),
+            Ok(Fetched::Existing) => println!("day{day}: {} already exists", path.display()),
+            Err(e) => {
+                eprintln!("day{day}: could not fetch the input: {e}");
+                code = ExitCode::FAILURE;
+                if matches!(e, Error::RateLimited(_)) {
+                    break;
+                }
             },
-        )*
-    }
-});
-
-fn main() {
-    let cli = Cli::parse();
-    seq!( N in 1..=25 {
-        match &cli.command {
-            #(
-                Commands::Day~N{part,input} => {
-                    let input:Vec<String> = read_lines(input)
-                        .unwrap()
-                        .map(|l|l.unwrap())
-                        .collect();
-                    match part{
-                        Part::Part1 => println!("{:?}",day~N::solution1(input)),
-                        Part::Part2 => println!("{:?}",day~N::solution2(input)),
-                    }
-                },
-            )*
         }
+    }
+
+    code
+}
+
+fn submit(
+    calendar: &Calendar,
+    day: u8,
+    part: Part,
+    input: Option<PathBuf>,
+    config: PathBuf,
+) -> ExitCode {
+    let path = calendar.inputs().join(history::FILE);
+    let opened = Client::load(&config).and_then(|client| {
+        let history =
+            History::load(&path).map_err(|e| format!("could not read {}: {e}", path.display()))?;
+        let (puzzle, input) = calendar.open(day, input.as_deref())?;
+        Ok((client, history, puzzle, input))
     });
+    let (client, mut history, puzzle, input) = match opened {
+        Ok(opened) => opened,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    runner::silence_panics();
+    let report = runner::run(puzzle, &[part], &input).remove(0);
+    let Outcome::Solved(answer) = report.outcome.clone() else {
+        eprintln!(
+            "could not solve day{day} part{}: {}",
+            part.number(),
+            verify::details(&report, None)
+        );
+        return ExitCode::FAILURE;
+    };
+    println!("day{day} part{}: {answer}", part.number());
+
+    let submission = match history.submit(&client, calendar.year, day, part, answer) {
+        Ok(submission) => submission,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        },
+    };
+    if let Err(e) = history.save() {
+        eprintln!("could not write {}: {e}", path.display());
+    }
+
+    println!("{}", submission.verdict);
+    if let Some(wait) = submission.wait {
+        println!("wait {}s before submitting again", wait.as_secs());
+    }
+
+    exit_code(submission.verdict == Verdict::Correct)
+}
+
+fn manage_cache(command: CacheCommand) -> ExitCode {
+    let path = Path::new(cache::PATH);
+    let result = match command {
+        CacheCommand::List => Cache::load(path).map(|cache| {
+            if cache.entries().is_empty() {
+                println!("no cached answers");
+            } else {
+                cache::print_entries(cache.entries());
+            }
+        }),
+        CacheCommand::Clear => Cache::clear(path).map(|cleared| {
+            if cleared {
+                println!("removed {}", path.display());
+            } else {
+                println!("no cached answers");
+            }
+        }),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("could not access {}: {e}", path.display());
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// Parse the command line, where the arguments can be prefixed by the year
+/// of the event, defaulting to the latest one.
+fn parse_cli() -> (&'static Calendar, Cli) {
+    let (calendar, args) = match calendar::select(env::args_os().collect()) {
+        Ok(selected) => selected,
+        Err(e) => Cli::command().error(ErrorKind::InvalidValue, e).exit(),
+    };
+    CALENDAR.get_or_init(|| calendar);
+
+    let years: Vec<String> = calendar::YEARS.iter().map(|c| c.year.to_string()).collect();
+    let matches = Cli::command()
+        .after_help(format!(
+            "Start the arguments with the year of an event to run its puzzles, one of {}, the \
+             latest by default.",
+            years.join(", ")
+        ))
+        .get_matches_from(args);
+
+    match Cli::from_arg_matches(&matches) {
+        Ok(cli) => (calendar, cli),
+        Err(e) => e.exit(),
+    }
+}
+
+fn main() -> ExitCode {
+    let (calendar, cli) = parse_cli();
+    match (cli.command, cli.run) {
+        (
+            Some(Commands::All {
+                solve,
+                output,
+                no_cache,
+            }),
+            _,
+        ) => all(calendar, solve, output, no_cache),
+        (
+            Some(Commands::Verify {
+                days,
+                solve,
+                limit,
+                answers,
+            }),
+            _,
+        ) => verify(calendar, days, solve, answers, limit.timeout),
+        (Some(Commands::Example { days }), _) => example(calendar, days),
+        (Some(Commands::New { day, pest, root }), _) => new(calendar, day, pest, root),
+        (Some(Commands::Bench(args)), _) => bench(calendar, args),
+        (Some(Commands::Fetch { days, config }), _) => fetch(calendar, days, config),
+        (
+            Some(Commands::Submit {
+                day,
+                part,
+                input,
+                config,
+            }),
+            _,
+        ) => submit(calendar, day, part, input, config),
+        (Some(Commands::Cache { command }), _) => manage_cache(command),
+        (None, Some(args)) if cli.watch => watch(calendar, args),
+        (None, Some(args)) => run(calendar, args, cli.output),
+        (None, None) => unreachable!(),
+    }
 }
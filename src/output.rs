@@ -0,0 +1,142 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::answer::Answer;
+use crate::runner::{Outcome, Report};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human readable output
+    #[default]
+    Text,
+    /// A single array with one object per part
+    Json,
+    /// One row per part, with a header
+    Csv,
+}
+
+/// Flat view of a [`Report`], shared by the machine readable formats.
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u8,
+    part: u8,
+    /// `solved`, `skipped`, `failed` or `timed_out`.
+    status: &'static str,
+    answer: Option<&'a Answer>,
+    reason: Option<String>,
+    parse_ns: u64,
+    solve_ns: u64,
+    input: Option<&'a Path>,
+    cached: bool,
+}
+
+impl<'a> From<&'a Report> for Record<'a> {
+    fn from(report: &'a Report) -> Self {
+        let (status, answer, reason) = match &report.outcome {
+            Outcome::Solved(answer) => ("solved", Some(answer), None),
+            Outcome::Skipped(reason) => ("skipped", None, Some(reason.clone())),
+            Outcome::Invalid(error) => ("failed", None, Some(error.to_string())),
+            Outcome::Failed(reason) => ("failed", None, Some(reason.clone())),
+            Outcome::TimedOut => ("timed_out", None, None),
+        };
+
+        Record {
+            day: report.day,
+            part: report.part.number(),
+            status,
+            answer,
+            reason,
+            parse_ns: report.parse.as_nanos() as u64,
+            solve_ns: report.elapsed.as_nanos() as u64,
+            input: report.input.as_deref(),
+            cached: report.cached,
+        }
+    }
+}
+
+pub fn write_json<W: Write>(writer: W, reports: &[Report]) -> io::Result<()> {
+    let records: Vec<Record> = reports.iter().map(Record::from).collect();
+    let mut writer = writer;
+    serde_json::to_writer_pretty(&mut writer, &records)?;
+    writeln!(writer)
+}
+
+pub fn write_csv<W: Write>(writer: W, reports: &[Report]) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for report in reports {
+        writer.serialize(Record::from(report))?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::solution::Part;
+
+    fn reports() -> Vec<Report> {
+        vec![
+            Report {
+                outcome: Outcome::Solved(46u32.into()),
+                parse: Duration::from_nanos(1500),
+                elapsed: Duration::from_micros(2),
+                input: Some(PathBuf::from("inputs/2023/day5.txt")),
+                ..Report::skipped(5, Part::Part2, "")
+            },
+            Report {
+                outcome: Outcome::Failed("invalid number, `x`".into()),
+                ..Report::skipped(6, Part::Part1, "")
+            },
+            Report {
+                cached: true,
+                ..Report::timed_out(7, Part::Part1, Duration::from_secs(1))
+            },
+        ]
+    }
+
+    #[test]
+    fn json_is_a_single_array() {
+        let mut json = Vec::new();
+        write_json(&mut json, &reports()).unwrap();
+        let records: serde_json::Value = serde_json::from_slice(&json).unwrap();
+
+        assert_eq!(
+            records,
+            serde_json::json!([
+                {
+                    "day": 5, "part": 2, "status": "solved", "answer": 46, "reason": null,
+                    "parse_ns": 1500, "solve_ns": 2000, "input": "inputs/2023/day5.txt",
+                    "cached": false,
+                },
+                {
+                    "day": 6, "part": 1, "status": "failed", "answer": null,
+                    "reason": "invalid number, `x`", "parse_ns": 0, "solve_ns": 0,
+                    "input": null, "cached": false,
+                },
+                {
+                    "day": 7, "part": 1, "status": "timed_out", "answer": null, "reason": null,
+                    "parse_ns": 0, "solve_ns": 1000000000, "input": null, "cached": true,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn csv_has_a_header_and_a_row_per_part() {
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &reports()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "day,part,status,answer,reason,parse_ns,solve_ns,input,cached\n5,2,solved,46,,1500,\
+             2000,inputs/2023/day5.txt,false\n6,1,failed,,\"invalid number, \
+             `x`\",0,0,,false\n7,1,timed_out,,,0,1000000000,,true\n"
+        );
+    }
+}
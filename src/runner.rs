@@ -0,0 +1,788 @@
+use std::any::Any;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::cancel::Token;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Parsed, Part, Puzzle};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Solved(Answer),
+    /// The day or part has not been written yet.
+    Skipped(String),
+    /// The input does not parse, at the location pointed by the error.
+    Invalid(ParseError),
+    Failed(String),
+    /// The part was abandoned after running for longer than its budget.
+    TimedOut,
+}
+
+pub struct Report {
+    pub day: u8,
+    pub part: Part,
+    pub outcome: Outcome,
+    /// Time spent parsing the input, shared by every part of the day.
+    pub parse: Duration,
+    pub elapsed: Duration,
+    /// File the input was read from, if any.
+    pub input: Option<PathBuf>,
+    /// Whether the answer comes from the cache rather than from solving.
+    pub cached: bool,
+}
+
+impl Report {
+    pub fn skipped(day: u8, part: Part, reason: &str) -> Self {
+        Report {
+            day,
+            part,
+            outcome: Outcome::Skipped(reason.to_string()),
+            parse: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            input: None,
+            cached: false,
+        }
+    }
+
+    /// Report of a part given up after `timeout`.
+    pub fn timed_out(day: u8, part: Part, timeout: Duration) -> Self {
+        Report {
+            outcome: Outcome::TimedOut,
+            elapsed: timeout,
+            ..Report::skipped(day, part, "")
+        }
+    }
+}
+
+/// Whether a part of `reports` failed or ran past its budget.
+pub fn failed(reports: &[Report]) -> bool {
+    reports.iter().any(|r| {
+        matches!(
+            r.outcome,
+            Outcome::Invalid(_) | Outcome::Failed(_) | Outcome::TimedOut
+        )
+    })
+}
+
+/// Message carried by the payload of a panic.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+fn panic_outcome(payload: Box<dyn Any + Send>) -> Outcome {
+    let message = panic_message(&*payload);
+    if message.starts_with("not yet implemented") {
+        Outcome::Skipped(message)
+    } else {
+        Outcome::Failed(message)
+    }
+}
+
+/// Keep panics caught by [`run`] and [`with_timeout`] from being printed to
+/// stderr, as they are reported along with the other failures.
+pub fn silence_panics() { panic::set_hook(Box::new(|_| {})); }
+
+/// Call `f` on another thread and wait at most `timeout` for its result,
+/// returning `None` if it takes longer. The late thread is then cancelled at
+/// its next [`checkpoint`](crate::cancel::checkpoint). A panic of `f` is caught
+/// and its payload returned as an error.
+///
+/// Without a timeout, `f` is simply called on the current thread.
+pub fn with_timeout<R, F>(timeout: Option<Duration>, f: F) -> Option<thread::Result<R>>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    let Some(timeout) = timeout else {
+        return Some(panic::catch_unwind(AssertUnwindSafe(f)));
+    };
+
+    let token = Token::default();
+    let attached = token.clone();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        attached.attach();
+        sender.send(panic::catch_unwind(AssertUnwindSafe(f)))
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Some(result),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            None
+        },
+        Err(RecvTimeoutError::Disconnected) => {
+            Some(Err(Box::new("solving thread stopped without a result")))
+        },
+    }
+}
+
+/// Parse `input`, catching any panic, along with the time it took.
+fn parse(puzzle: &dyn Puzzle, input: &Input) -> (Result<Parsed, Outcome>, Duration) {
+    let start = Instant::now();
+    let parsed = panic::catch_unwind(AssertUnwindSafe(|| puzzle.parse(input)));
+    let parse = start.elapsed();
+
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => Ok(parsed),
+        Ok(Err(e)) => Err(Outcome::Invalid(e)),
+        Err(payload) => Err(panic_outcome(payload)),
+    };
+    (parsed, parse)
+}
+
+/// Solve `part` from `parsed`, catching any panic, along with the time it
+/// took.
+fn solve(puzzle: &dyn Puzzle, part: Part, parsed: &Parsed) -> (Outcome, Duration) {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| puzzle.solve(part, parsed)));
+    let elapsed = start.elapsed();
+
+    let outcome = match result {
+        Ok(Ok(Answer::Unimplemented)) => Outcome::Skipped("unimplemented".to_string()),
+        Ok(Ok(answer)) => Outcome::Solved(answer),
+        Ok(Err(reason)) => Outcome::Failed(reason),
+        Err(payload) => panic_outcome(payload),
+    };
+    (outcome, elapsed)
+}
+
+/// Parse `input` once and solve each of `parts`, catching any panic along the
+/// way.
+pub fn run(puzzle: &dyn Puzzle, parts: &[Part], input: &Input) -> Vec<Report> {
+    let day = puzzle.metadata().day;
+
+    if !puzzle.implemented() {
+        return parts
+            .iter()
+            .map(|&part| Report {
+                input: input.path().map(Into::into),
+                ..Report::skipped(day, part, "unimplemented")
+            })
+            .collect();
+    }
+
+    let (parsed, parse) = parse(puzzle, input);
+    let report = |part, outcome, elapsed| Report {
+        day,
+        part,
+        outcome,
+        parse,
+        elapsed,
+        input: input.path().map(Into::into),
+        cached: false,
+    };
+
+    match parsed {
+        Ok(parsed) => parts
+            .iter()
+            .map(|&part| {
+                let (outcome, elapsed) = solve(puzzle, part, &parsed);
+                report(part, outcome, elapsed)
+            })
+            .collect(),
+        Err(outcome) => parts
+            .iter()
+            .map(|&part| report(part, outcome.clone(), Duration::ZERO))
+            .collect(),
+    }
+}
+
+/// Input of a day parsed once, from which each of its parts can be solved on
+/// its own, possibly on another thread.
+struct Prepared {
+    puzzle: &'static dyn Puzzle,
+    /// Parsed model, or the outcome of every part if parsing failed.
+    parsed: Result<Arc<Parsed>, Outcome>,
+    parse: Duration,
+    input: Option<PathBuf>,
+}
+
+impl Prepared {
+    /// Parse `input`, giving up once it runs past `timeout`.
+    fn new(puzzle: &'static dyn Puzzle, input: Input, timeout: Option<Duration>) -> Self {
+        let path = input.path().map(Into::into);
+        let (parsed, parse) = if !puzzle.implemented() {
+            (
+                Err(Outcome::Skipped("unimplemented".to_string())),
+                Duration::ZERO,
+            )
+        } else {
+            match with_timeout(timeout, move || parse(puzzle, &input)) {
+                Some(Ok((parsed, parse))) => (parsed.map(Arc::new), parse),
+                Some(Err(payload)) => (Err(panic_outcome(payload)), Duration::ZERO),
+                None => (Err(Outcome::TimedOut), timeout.unwrap()),
+            }
+        };
+
+        Prepared {
+            puzzle,
+            parsed,
+            parse,
+            input: path,
+        }
+    }
+
+    /// Reports of a day whose `input` cannot be parsed, all with `outcome`.
+    fn skipped(puzzle: &'static dyn Puzzle, input: &Path, outcome: Outcome) -> Self {
+        Prepared {
+            puzzle,
+            parsed: Err(outcome),
+            parse: Duration::ZERO,
+            input: Some(input.into()),
+        }
+    }
+
+    /// Solve `part`, giving up once it runs past `timeout`.
+    fn solve(&self, part: Part, timeout: Option<Duration>) -> Report {
+        let (outcome, elapsed) = match &self.parsed {
+            Ok(parsed) => {
+                let (puzzle, parsed) = (self.puzzle, Arc::clone(parsed));
+                match with_timeout(timeout, move || solve(puzzle, part, &parsed)) {
+                    Some(Ok(solved)) => solved,
+                    Some(Err(payload)) => (panic_outcome(payload), Duration::ZERO),
+                    None => (Outcome::TimedOut, timeout.unwrap()),
+                }
+            },
+            Err(Outcome::TimedOut) => (Outcome::TimedOut, self.parse),
+            Err(outcome) => (outcome.clone(), Duration::ZERO),
+        };
+
+        Report {
+            day: self.puzzle.metadata().day,
+            part,
+            outcome,
+            parse: self.parse,
+            elapsed,
+            input: self.input.clone(),
+            cached: false,
+        }
+    }
+}
+
+/// Like [`run`], giving up on the parsing and on each part once they run
+/// past `timeout`. The model is still parsed once and shared by the parts.
+pub fn run_within(
+    puzzle: &'static dyn Puzzle,
+    parts: &[Part],
+    input: Input,
+    timeout: Option<Duration>,
+) -> Vec<Report> {
+    let prepared = Prepared::new(puzzle, input, timeout);
+    parts
+        .iter()
+        .map(|&part| prepared.solve(part, timeout))
+        .collect()
+}
+
+/// Answer, parse time and solve time of `report`, as displayed in tables.
+fn cells(report: &Report) -> (String, String, String) {
+    match &report.outcome {
+        Outcome::Solved(answer) if report.cached => {
+            (answer.to_string(), "-".to_string(), "cached".to_string())
+        },
+        Outcome::Solved(answer) => (
+            answer.to_string(),
+            format!("{:.2?}", report.parse),
+            format!("{:.2?}", report.elapsed),
+        ),
+        Outcome::Skipped(reason) => (
+            format!("skipped ({reason})"),
+            "-".to_string(),
+            "-".to_string(),
+        ),
+        Outcome::Invalid(error) => (
+            format!("failed ({error})"),
+            "-".to_string(),
+            "-".to_string(),
+        ),
+        Outcome::Failed(reason) => (
+            format!("failed ({reason})"),
+            "-".to_string(),
+            "-".to_string(),
+        ),
+        Outcome::TimedOut => (
+            "timed out".to_string(),
+            "-".to_string(),
+            format!("> {:.2?}", report.elapsed),
+        ),
+    }
+}
+
+fn answer_width(rows: &[(String, String, String)]) -> usize {
+    rows.iter()
+        .map(|(answer, ..)| answer.len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len())
+}
+
+/// Apply `f` to every item on up to `jobs` threads, keeping the order of
+/// `items` in the results.
+pub fn parallel_map<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let workers = jobs.clamp(1, items.len().max(1));
+
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break;
+                        };
+                        results.push((index, f(item)));
+                    }
+                    results
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    results.sort_by_key(|&(index, _)| index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Parts of a day to solve from a single parsing of the file holding its
+/// input.
+#[derive(Clone)]
+pub struct Task {
+    pub puzzle: &'static dyn Puzzle,
+    pub parts: Vec<Part>,
+    pub input: PathBuf,
+}
+
+impl Task {
+    /// Both parts of each of `puzzles`, reading the input of day N from
+    /// `inputs/dayN.txt`.
+    pub fn days(puzzles: &[&'static dyn Puzzle], inputs: &Path) -> Vec<Task> {
+        puzzles
+            .iter()
+            .map(|&puzzle| Task {
+                puzzle,
+                parts: Part::ALL.to_vec(),
+                input: inputs.join(format!("day{}.txt", puzzle.metadata().day)),
+            })
+            .collect()
+    }
+
+    /// Read and parse the input, giving up once it runs past `timeout`. The
+    /// parts are skipped if the input is missing.
+    fn prepare(&self, timeout: Option<Duration>) -> Prepared {
+        match Input::read(&self.input) {
+            Ok(input) => Prepared::new(self.puzzle, input, timeout),
+            Err(_) => {
+                let reason =
+                    if self.puzzle.implemented() { "missing input" } else { "unimplemented" };
+                let outcome = Outcome::Skipped(reason.to_string());
+                Prepared::skipped(self.puzzle, &self.input, outcome)
+            },
+        }
+    }
+}
+
+/// Work waiting for a thread of [`run_tasks`].
+enum Job {
+    /// Read and parse the input of the task at this index.
+    Prepare(usize),
+    /// Solve a part of a parsed day, reported at the given index.
+    Solve(Arc<Prepared>, Part, usize),
+}
+
+/// Jobs of [`run_tasks`] not yet picked, along with the number of jobs queued
+/// or running, which drops to zero once every report is written.
+struct Queue {
+    jobs: VecDeque<Job>,
+    pending: usize,
+}
+
+/// Run `tasks` on up to `jobs` threads, returning their reports in order.
+/// Each input is parsed once, and the parts of a day are queued as soon as it
+/// is parsed, ahead of the days left to parse. The parts of a slow day thus do
+/// not wait for each other, nor for the parsing of the other days. A part that
+/// runs past `timeout` is reported as such, without holding up the others.
+pub fn run_tasks(tasks: &[Task], jobs: usize, timeout: Option<Duration>) -> Vec<Report> {
+    let mut offsets = Vec::with_capacity(tasks.len());
+    let mut total = 0;
+    for task in tasks {
+        offsets.push(total);
+        total += task.parts.len();
+    }
+
+    let prepare: VecDeque<Job> = (0..tasks.len())
+        .filter(|&index| !tasks[index].parts.is_empty())
+        .map(Job::Prepare)
+        .collect();
+    let queue = Mutex::new(Queue {
+        pending: prepare.len(),
+        jobs: prepare,
+    });
+    let ready = Condvar::new();
+    let reports: Mutex<Vec<Option<Report>>> = Mutex::new((0..total).map(|_| None).collect());
+
+    let work = || loop {
+        let job = {
+            let mut queue = queue.lock().unwrap();
+            loop {
+                if let Some(job) = queue.jobs.pop_front() {
+                    break job;
+                }
+                if queue.pending == 0 {
+                    return;
+                }
+                queue = ready.wait(queue).unwrap();
+            }
+        };
+
+        let mut queue = match job {
+            Job::Prepare(index) => {
+                let task = &tasks[index];
+                let prepared = Arc::new(task.prepare(timeout));
+                let mut queue = queue.lock().unwrap();
+                for (offset, &part) in task.parts.iter().enumerate().rev() {
+                    let job = Job::Solve(Arc::clone(&prepared), part, offsets[index] + offset);
+                    queue.jobs.push_front(job);
+                }
+                queue.pending += task.parts.len();
+                queue
+            },
+            Job::Solve(prepared, part, slot) => {
+                let report = prepared.solve(part, timeout);
+                reports.lock().unwrap()[slot] = Some(report);
+                queue.lock().unwrap()
+            },
+        };
+        queue.pending -= 1;
+        ready.notify_all();
+    };
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, total.max(1)) {
+            scope.spawn(work);
+        }
+    });
+
+    reports
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|report| report.expect("every part is solved"))
+        .collect()
+}
+
+/// Solve `parts` on each of `paths` in parallel, keeping their order. A file
+/// that cannot be read only fails its own reports, and one that takes longer
+/// than `timeout` only times out its own.
+pub fn run_files(
+    puzzle: &'static dyn Puzzle,
+    parts: &[Part],
+    paths: &[PathBuf],
+    timeout: Option<Duration>,
+) -> Vec<Report> {
+    let day = puzzle.metadata().day;
+    let jobs = thread::available_parallelism().map_or(1, usize::from);
+
+    parallel_map(paths, jobs, |path| match Input::read(path) {
+        Ok(input) => run_within(puzzle, parts, input, timeout),
+        Err(e) => parts
+            .iter()
+            .map(|&part| Report {
+                outcome: Outcome::Failed(format!("could not read: {e}")),
+                input: Some(path.clone()),
+                ..Report::skipped(day, part, "")
+            })
+            .collect(),
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+pub fn print_table(reports: &[Report]) {
+    let rows: Vec<_> = reports.iter().map(cells).collect();
+    let width = answer_width(&rows);
+
+    println!(
+        "{:>4} {:>4}  {:<width$}  {:>10}  {:>10}",
+        "Day", "Part", "Answer", "Parse", "Solve"
+    );
+    for (report, (answer, parse, solve)) in reports.iter().zip(rows) {
+        println!(
+            "{:>4} {:>4}  {:<width$}  {:>10}  {:>10}",
+            report.day,
+            report.part.number(),
+            answer,
+            parse,
+            solve
+        );
+    }
+}
+
+/// Like [`print_table`], for reports of a single day over several inputs.
+pub fn print_file_table(reports: &[Report]) {
+    let rows: Vec<_> = reports.iter().map(cells).collect();
+    let width = answer_width(&rows);
+    let files: Vec<String> = reports
+        .iter()
+        .map(|r| {
+            r.input
+                .as_ref()
+                .map_or("-".into(), |p| p.display().to_string())
+        })
+        .collect();
+    let file_width = files
+        .iter()
+        .map(String::len)
+        .max()
+        .unwrap_or(0)
+        .max("File".len());
+
+    println!(
+        "{:<file_width$} {:>4}  {:<width$}  {:>10}  {:>10}",
+        "File", "Part", "Answer", "Parse", "Solve"
+    );
+    for ((report, file), (answer, parse, solve)) in reports.iter().zip(files).zip(rows) {
+        println!(
+            "{:<file_width$} {:>4}  {:<width$}  {:>10}  {:>10}",
+            file,
+            report.part.number(),
+            answer,
+            parse,
+            solve
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::atomic::AtomicBool;
+
+    use super::*;
+    use crate::cancel::checkpoint;
+    use crate::error::ParseError;
+    use crate::fixture::{Counted, Scratch};
+    use crate::solution::{Metadata, Solution};
+
+    static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+    /// Answers with the number of its parts seen running at the same time.
+    struct Paired;
+
+    impl Paired {
+        fn meet() -> usize {
+            RUNNING.fetch_add(1, Ordering::SeqCst);
+            let start = Instant::now();
+            while RUNNING.load(Ordering::SeqCst) < 2 && start.elapsed() < Duration::from_secs(10) {
+                thread::sleep(Duration::from_millis(1));
+            }
+            RUNNING.load(Ordering::SeqCst)
+        }
+    }
+
+    impl Solution for Paired {
+        type Model = ();
+        type Output = usize;
+
+        const METADATA: Metadata = Metadata {
+            day: 2,
+            title: "Paired",
+        };
+
+        fn parse(_: &Input) -> Result<(), ParseError> { Ok(()) }
+
+        fn part1(_: &()) -> usize { Paired::meet() }
+
+        fn part2(_: &()) -> usize { Paired::meet() }
+    }
+
+    static SOLVED: AtomicBool = AtomicBool::new(false);
+
+    /// Parses an input reading `wait` only once a part of another input was
+    /// solved, answering with whether that happened in time.
+    struct Relay;
+
+    impl Solution for Relay {
+        type Model = bool;
+        type Output = usize;
+
+        const METADATA: Metadata = Metadata {
+            day: 3,
+            title: "Relay",
+        };
+
+        fn parse(input: &Input) -> Result<bool, ParseError> {
+            let start = Instant::now();
+            while input.text().trim() == "wait" && !SOLVED.load(Ordering::SeqCst) {
+                if start.elapsed() > Duration::from_secs(10) {
+                    return Ok(false);
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+            Ok(true)
+        }
+
+        fn part1(in_time: &bool) -> usize {
+            SOLVED.store(true, Ordering::SeqCst);
+            usize::from(*in_time)
+        }
+
+        fn part2(in_time: &bool) -> usize { usize::from(*in_time) }
+    }
+
+    /// Answers its first part only, explaining why the second has no answer.
+    struct Partial;
+
+    impl Solution for Partial {
+        type Model = ();
+        type Output = Result<u32, String>;
+
+        const METADATA: Metadata = Metadata {
+            day: 4,
+            title: "Partial",
+        };
+
+        fn parse(_: &Input) -> Result<(), ParseError> { Ok(()) }
+
+        fn part1(_: &()) -> Result<u32, String> { Ok(1) }
+
+        fn part2(_: &()) -> Result<u32, String> { Err("too large".to_string()) }
+    }
+
+    fn answers(reports: &[Report]) -> Vec<Outcome> {
+        reports.iter().map(|r| r.outcome.clone()).collect()
+    }
+
+    #[test]
+    fn parts_can_fail_with_an_error() {
+        let reports = run(&Partial, &Part::ALL, &Input::from(""));
+        assert_eq!(
+            answers(&reports),
+            [
+                Outcome::Solved(1u32.into()),
+                Outcome::Failed("too large".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn tasks_parse_their_input_once() {
+        let dir = Scratch::new("runner-tasks");
+        let input = dir.join("day1.txt");
+        fs::write(&input, "abc\n").unwrap();
+        let task = Task {
+            puzzle: &Counted,
+            parts: Part::ALL.to_vec(),
+            input: input.clone(),
+        };
+
+        for timeout in [None, Some(Duration::from_secs(60))] {
+            let reports = run_tasks(std::slice::from_ref(&task), 2, timeout);
+            assert_eq!(
+                answers(&reports),
+                [Outcome::Solved(3u32.into()), Outcome::Solved(6u32.into())]
+            );
+        }
+        assert_eq!(Counted::parses(&input), 2);
+    }
+
+    #[test]
+    fn parts_of_a_day_run_at_the_same_time() {
+        let dir = Scratch::new("runner-parts");
+        let input = dir.join("day2.txt");
+        fs::write(&input, "").unwrap();
+        let task = Task {
+            puzzle: &Paired,
+            parts: Part::ALL.to_vec(),
+            input,
+        };
+
+        let reports = run_tasks(&[task], 2, None);
+        assert_eq!(
+            answers(&reports),
+            [Outcome::Solved(2u32.into()), Outcome::Solved(2u32.into())]
+        );
+    }
+
+    #[test]
+    fn parts_do_not_wait_for_the_parsing_of_other_days() {
+        let dir = Scratch::new("runner-relay");
+        let task = |name: &str, text: &str| {
+            let input = dir.join(name);
+            fs::write(&input, text).unwrap();
+            Task {
+                puzzle: &Relay,
+                parts: Part::ALL.to_vec(),
+                input,
+            }
+        };
+
+        let reports = run_tasks(&[task("slow.txt", "wait"), task("fast.txt", "go")], 2, None);
+        assert_eq!(answers(&reports), vec![Outcome::Solved(1u32.into()); 4]);
+    }
+
+    #[test]
+    fn missing_inputs_keep_their_path() {
+        let dir = Scratch::new("runner-missing");
+        let task = Task {
+            puzzle: &Counted,
+            parts: Part::ALL.to_vec(),
+            input: dir.join("day1.txt"),
+        };
+
+        for report in run_tasks(std::slice::from_ref(&task), 1, None) {
+            assert_eq!(report.outcome, Outcome::Skipped("missing input".into()));
+            assert_eq!(report.input.as_ref(), Some(&task.input));
+        }
+    }
+
+    #[test]
+    fn panics_are_returned_as_errors() {
+        for timeout in [None, Some(Duration::from_secs(60))] {
+            let payload = with_timeout(timeout, || -> u32 { panic!("no start") })
+                .unwrap()
+                .unwrap_err();
+            assert_eq!(panic_message(&*payload), "no start");
+        }
+    }
+
+    #[test]
+    fn late_threads_are_cancelled() {
+        let (sender, receiver) = mpsc::channel();
+        let solved = with_timeout(Some(Duration::from_millis(10)), move || {
+            let result = panic::catch_unwind(|| loop {
+                checkpoint();
+                thread::sleep(Duration::from_millis(1));
+            });
+            sender.send(result.is_err()).unwrap();
+        });
+
+        assert!(solved.is_none());
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(60)), Ok(true));
+    }
+}
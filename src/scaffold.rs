@@ -0,0 +1,355 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// Generate the module of `day` in the `module` of the calendar under
+/// `root`, along with its example stub,
+/// its grammar when `pest` is set, and register it in the registry and the
+/// example tests, where it is ignored until the expected answers of its
+/// examples are filled in. Returns the files that were created.
+///
+/// Nothing is written if the day already has a module.
+pub fn scaffold(
+    root: &Path,
+    module: &str,
+    day: u8,
+    title: &str,
+    pest: bool,
+) -> io::Result<Vec<PathBuf>> {
+    let days = root.join("src").join(module);
+    let source = days.join(format!("day{day}.rs"));
+    let grammar = days.join(format!("day{day}.pest"));
+    let example = days.join(format!("examples/day{day}.txt"));
+    let registry = days.join("mod.rs");
+    let tests = root.join("tests/examples.rs");
+
+    let mut created = vec![source.clone(), example.clone()];
+    if pest {
+        created.push(grammar.clone());
+    }
+    if let Some(existing) = created.iter().find(|path| path.exists()) {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} already exists", existing.display()),
+        ));
+    }
+
+    let registry_content = register(&fs::read_to_string(&registry)?, day)?;
+    let tests_content = register_test(&fs::read_to_string(&tests)?, day)?;
+
+    fs::write(&source, module_template(module, day, title, pest))?;
+    fs::write(&example, "")?;
+    if pest {
+        fs::write(&grammar, GRAMMAR_TEMPLATE)?;
+    }
+    fs::write(&registry, registry_content)?;
+    fs::write(&tests, tests_content)?;
+
+    Ok(created)
+}
+
+/// Declare the module of `day` and swap its placeholder in the registry.
+fn register(registry: &str, day: u8) -> io::Result<String> {
+    let placeholder = format!("    &Unimplemented(Metadata {{\n        day: {day},\n");
+    let start = registry.find(&placeholder).ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("day{day} is already registered"),
+        )
+    })?;
+    let end = registry[start..].find("}),\n").ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::InvalidData,
+            format!("the placeholder of day{day} is not closed"),
+        )
+    })?;
+    let end = start + end + "}),\n".len();
+
+    let mut content = format!(
+        "{}    &day{day}::Day{day},\n{}",
+        &registry[..start],
+        &registry[end..]
+    );
+    if !content.contains("&Unimplemented(") {
+        content = content.replace(
+            "use crate::solution::{Metadata, Puzzle, Unimplemented};",
+            "use crate::solution::Puzzle;",
+        );
+    }
+
+    // Modules are kept in the order rustfmt sorts them.
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let name = format!("day{day}");
+    let first = lines.iter().position(|l| module(l).is_some());
+    let last = lines.iter().rposition(|l| module(l).is_some());
+    let position = match first.zip(last) {
+        Some((first, last)) => lines[first..=last]
+            .iter()
+            .position(|l| module(l).is_some_and(|m| m > name.as_str()))
+            .map_or(last + 1, |p| first + p),
+        // The first module goes in its own paragraph after the imports.
+        None => {
+            let imports = lines
+                .iter()
+                .rposition(|l| l.starts_with("use "))
+                .map_or(0, |n| n + 1);
+            lines.insert(imports, String::new());
+            imports + 1
+        },
+    };
+    lines.insert(position, format!("pub mod {name};"));
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Name of the module declared by `line`, if any.
+fn module(line: &str) -> Option<&str> { line.strip_prefix("pub mod ")?.strip_suffix(';') }
+
+/// Attribute of the example test of a new day, which has no expected answers
+/// yet.
+const IGNORED: &str = "#[ignore = \"fill in the expected answers of the examples\"]";
+
+/// Add `day` to the days checked by the example tests, ignored until its
+/// examples have expected answers.
+fn register_test(tests: &str, day: u8) -> io::Result<String> {
+    let mut lines: Vec<String> = tests.lines().map(str::to_string).collect();
+    let number = |line: &str| {
+        let (_, day) = line.trim().trim_end_matches(',').split_once(" => ")?;
+        day.parse::<u8>().ok()
+    };
+    if lines.iter().any(|l| number(l) == Some(day)) {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("day{day} already has example tests"),
+        ));
+    }
+    // Without any day yet, the first one opens the list.
+    let last = lines
+        .iter()
+        .rposition(|l| number(l).is_some() || l.starts_with("examples! {"))
+        .ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                "no `examples!` list in the example tests",
+            )
+        })?;
+    let mut position = lines
+        .iter()
+        .position(|l| number(l).is_some_and(|n| n > day))
+        .unwrap_or(last + 1);
+    // Keep the attributes of the next day above it.
+    while position > 0 && lines[position - 1].trim_start().starts_with("#[") {
+        position -= 1;
+    }
+    lines.insert(position, format!("    day{day} => {day},"));
+    lines.insert(position, format!("    {IGNORED}"));
+
+    Ok(lines.join("\n") + "\n")
+}
+
+const GRAMMAR_TEMPLATE: &str = "line = { SOI ~ ASCII_ALPHANUMERIC* ~ EOI }
+WHITESPACE = _{ \" \" }
+";
+
+fn module_template(module: &str, day: u8, title: &str, pest: bool) -> String {
+    let (imports, parser, parse) = if pest {
+        (
+            "use pest::Parser;\nuse pest_derive::Parser as DeriveParser;\n\n",
+            format!(
+                "#[derive(DeriveParser)]\n#[grammar = \"{module}/day{day}.pest\"]\npub struct \
+                 Day{day}Parser;\n\n"
+            ),
+            format!(
+                "fn parse(line: &str) -> Result<Line, ParseError> {{\n    \
+                 Day{day}Parser::parse(Rule::line, line)?;\n    Ok(Line)\n}}\n"
+            ),
+        )
+    } else {
+        (
+            "",
+            String::new(),
+            "fn parse(_line: &str) -> Result<Line, ParseError> { Ok(Line) }\n".to_string(),
+        )
+    };
+
+    format!(
+        "{imports}use crate::answer::Answer;
+use crate::error::{{parse_lines, ParseError}};
+use crate::input::Input;
+use crate::solution::{{Example, Metadata, Solution}};
+
+/// What a line of the input holds.
+pub struct Line;
+
+{parser}{parse}
+pub fn solution1(_input: &[Line]) -> Answer {{ Answer::Unimplemented }}
+
+pub fn solution2(_input: &[Line]) -> Answer {{ Answer::Unimplemented }}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    type Model = Vec<Line>;
+    type Output = Answer;
+
+    const EXAMPLES: &'static [Example] = &[Example {{
+        input: include_str!(\"examples/day{day}.txt\"),
+        part1: None,
+        part2: None,
+    }}];
+    const METADATA: Metadata = Metadata {{
+        day: {day},
+        title: {title:?},
+    }};
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError> {{ parse_lines(input.lines(), \
+         parse) }}
+
+    fn part1(input: &Self::Model) -> Answer {{ solution1(input) }}
+
+    fn part2(input: &Self::Model) -> Answer {{ solution2(input) }}
+}}
+"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::Scratch;
+
+    const REGISTRY: &str = "use crate::solution::{Metadata, Puzzle, Unimplemented};
+
+pub mod day1;
+pub mod day3;
+
+pub static DAYS: [&dyn Puzzle; 3] = [
+    &day1::Day1,
+    &Unimplemented(Metadata {
+        day: 2,
+        title: \"Two\",
+    }),
+    &day3::Day3,
+];
+";
+
+    const TESTS: &str = "examples! {
+    day1 => 1,
+    day3 => 3,
+}
+";
+
+    #[test]
+    fn register_swaps_the_placeholder() {
+        let registry = register(REGISTRY, 2).unwrap();
+        assert_eq!(
+            registry,
+            "use crate::solution::Puzzle;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+
+pub static DAYS: [&dyn Puzzle; 3] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+];
+"
+        );
+        assert_eq!(
+            register(&registry, 2).unwrap_err().kind(),
+            ErrorKind::AlreadyExists
+        );
+    }
+
+    #[test]
+    fn register_the_first_module() {
+        let registry = "use crate::solution::{Metadata, Puzzle, Unimplemented};
+
+pub static DAYS: [&dyn Puzzle; 2] = [
+    &Unimplemented(Metadata {
+        day: 1,
+        title: \"One\",
+    }),
+    &Unimplemented(Metadata {
+        day: 2,
+        title: \"Two\",
+    }),
+];
+";
+        let registry = register(registry, 1).unwrap();
+        assert!(registry.starts_with(
+            "use crate::solution::{Metadata, Puzzle, Unimplemented};\n\npub mod day1;\n\npub \
+             static"
+        ));
+        assert!(
+            registry.contains("    &day1::Day1,\n    &Unimplemented(Metadata {\n        day: 2,")
+        );
+    }
+
+    #[test]
+    fn register_rejects_an_unclosed_placeholder() {
+        let registry = "    &Unimplemented(Metadata {\n        day: 2,\n";
+        assert_eq!(
+            register(registry, 2).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn register_test_keeps_days_in_order() {
+        let ignored = format!("    {IGNORED}\n");
+        assert_eq!(
+            register_test(TESTS, 2).unwrap(),
+            format!("examples! {{\n    day1 => 1,\n{ignored}    day2 => 2,\n    day3 => 3,\n}}\n")
+        );
+        let five = register_test(TESTS, 5).unwrap();
+        assert_eq!(
+            register_test(&five, 4).unwrap(),
+            format!(
+                "examples! {{\n    day1 => 1,\n    day3 => 3,\n{ignored}    day4 => 4,\n{ignored}    \
+                 day5 => 5,\n}}\n"
+            )
+        );
+        assert_eq!(
+            register_test("examples! {\n}\n", 1).unwrap(),
+            format!("examples! {{\n{ignored}    day1 => 1,\n}}\n")
+        );
+        assert!(register_test(TESTS, 3).is_err());
+        assert!(register_test(&five, 5).is_err());
+        assert!(register_test("", 3).is_err());
+    }
+
+    #[test]
+    fn new_days_report_they_are_unimplemented() {
+        for pest in [false, true] {
+            let module = module_template("days", 2, "Two", pest);
+            assert!(module.contains("Answer::Unimplemented"));
+            assert!(!module.contains("todo!"));
+            assert!(!module.contains("String"));
+        }
+    }
+
+    #[test]
+    fn scaffold_writes_every_file_once() {
+        let root = Scratch::new("scaffold");
+        fs::create_dir_all(root.join("src/days/examples")).unwrap();
+        fs::create_dir_all(root.join("tests")).unwrap();
+        fs::write(root.join("src/days/mod.rs"), REGISTRY).unwrap();
+        fs::write(root.join("tests/examples.rs"), TESTS).unwrap();
+
+        let created = scaffold(&root, "days", 2, "Two", true).unwrap();
+
+        assert_eq!(created.len(), 3);
+        assert!(created.iter().all(|path| path.exists()));
+        let source = fs::read_to_string(root.join("src/days/day2.rs")).unwrap();
+        assert!(source.contains("#[grammar = \"days/day2.pest\"]"));
+        assert!(fs::read_to_string(root.join("tests/examples.rs"))
+            .unwrap()
+            .contains("day2 => 2"));
+
+        let error = scaffold(&root, "days", 2, "Two", false).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+    }
+}
@@ -0,0 +1,130 @@
+use std::any::Any;
+use std::fmt::Display;
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::Input;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    Part1,
+    Part2,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::Part1, Part::Part2];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::Part1 => 1,
+            Part::Part2 => 2,
+        }
+    }
+}
+
+/// Static information about a puzzle.
+#[derive(Clone, Copy, Debug)]
+pub struct Metadata {
+    pub day: u8,
+    pub title: &'static str,
+}
+
+/// Worked example from the puzzle text, with the answers it gives.
+///
+/// Some examples only apply to one of the parts, the other one is left empty.
+#[derive(Clone, Debug)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::Part1 => self.part1.as_ref(),
+            Part::Part2 => self.part2.as_ref(),
+        }
+    }
+}
+
+/// What a part gives back: an answer, or the reason it has none.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, String>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> Result<Answer, String> { Ok(self.into()) }
+}
+
+impl<T: Into<Answer>, E: Display> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Result<Answer, String> {
+        self.map(Into::into).map_err(|e| e.to_string())
+    }
+}
+
+/// A day of the calendar, split into a parsing step and the two parts.
+pub trait Solution {
+    const METADATA: Metadata;
+    const EXAMPLES: &'static [Example] = &[];
+
+    /// Representation shared by both parts.
+    type Model: Send + Sync + 'static;
+    type Output: IntoAnswer;
+
+    fn parse(input: &Input) -> Result<Self::Model, ParseError>;
+
+    fn part1(input: &Self::Model) -> Self::Output;
+
+    fn part2(input: &Self::Model) -> Self::Output;
+}
+
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+/// Object safe view of a [`Solution`], used by the registry.
+pub trait Puzzle: Sync {
+    fn metadata(&self) -> Metadata;
+
+    fn implemented(&self) -> bool { true }
+
+    fn examples(&self) -> &'static [Example] { &[] }
+
+    fn parse(&self, input: &Input) -> Result<Parsed, ParseError>;
+
+    /// Solve `part` from the output of [`Puzzle::parse`], or explain why it
+    /// has no answer.
+    fn solve(&self, part: Part, parsed: &Parsed) -> Result<Answer, String>;
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn metadata(&self) -> Metadata { S::METADATA }
+
+    fn examples(&self) -> &'static [Example] { S::EXAMPLES }
+
+    fn parse(&self, input: &Input) -> Result<Parsed, ParseError> { Ok(Box::new(S::parse(input)?)) }
+
+    fn solve(&self, part: Part, parsed: &Parsed) -> Result<Answer, String> {
+        let input = parsed
+            .downcast_ref::<S::Model>()
+            .expect("parsed input belongs to another day");
+        match part {
+            Part::Part1 => S::part1(input).into_answer(),
+            Part::Part2 => S::part2(input).into_answer(),
+        }
+    }
+}
+
+/// Placeholder for a day that has not been solved yet.
+pub struct Unimplemented(pub Metadata);
+
+impl Puzzle for Unimplemented {
+    fn metadata(&self) -> Metadata { self.0 }
+
+    fn implemented(&self) -> bool { false }
+
+    fn parse(&self, _input: &Input) -> Result<Parsed, ParseError> { Ok(Box::new(())) }
+
+    fn solve(&self, _part: Part, _parsed: &Parsed) -> Result<Answer, String> {
+        Ok(Answer::Unimplemented)
+    }
+}
@@ -0,0 +1,285 @@
+use std::path::Path;
+use std::time::Duration;
+use std::{fs, io};
+
+use serde::Deserialize;
+
+use crate::answer::Answer;
+use crate::calendar::Calendar;
+use crate::runner::{self, Outcome, Report, Task};
+
+/// Known answers, stored as a list of `[[answer]]` tables:
+///
+/// ```toml
+/// [[answer]]
+/// day = 5
+/// part = 2
+/// expected = 46
+/// ```
+#[derive(Deserialize)]
+pub struct Answers {
+    #[serde(rename = "answer", default)]
+    pub answers: Vec<Expected>,
+}
+
+#[derive(Deserialize)]
+pub struct Expected {
+    pub day: u8,
+    pub part: u8,
+    pub expected: Answer,
+}
+
+impl Answers {
+    pub fn load<P: AsRef<Path>>(path: P, calendar: &Calendar) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content, calendar).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Read answers from TOML, rejecting the ones for a day of `calendar` or a
+    /// part that does not exist.
+    pub fn parse(content: &str, calendar: &Calendar) -> Result<Self, String> {
+        let answers: Answers = toml::from_str(content).map_err(|e| e.to_string())?;
+        for answer in &answers.answers {
+            if !(1..=calendar.length()).contains(&answer.day) {
+                return Err(format!(
+                    "answer for day {} is not in day1..=day{} of {}",
+                    answer.day,
+                    calendar.length(),
+                    calendar.year
+                ));
+            }
+            if !(1..=2).contains(&answer.part) {
+                return Err(format!(
+                    "answer for part {} of day {} is not part1 or part2",
+                    answer.part, answer.day
+                ));
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.answers
+            .iter()
+            .find(|a| a.day == day && a.part == part)
+            .map(|a| &a.expected)
+    }
+
+    pub fn days(&self) -> Vec<u8> {
+        let mut days: Vec<u8> = self.answers.iter().map(|a| a.day).collect();
+        days.sort_unstable();
+        days.dedup();
+        days
+    }
+}
+
+pub enum Verdict {
+    Pass,
+    Mismatch,
+    /// There is no stored answer to compare against.
+    Unknown,
+    /// The part was expected to be solved, but panicked or was skipped.
+    Error,
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool { matches!(self, Verdict::Mismatch | Verdict::Error) }
+
+    pub fn status(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Mismatch | Verdict::Error => "FAIL",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+pub fn check(report: &Report, expected: Option<&Answer>) -> Verdict {
+    match (&report.outcome, expected) {
+        (_, None) => Verdict::Unknown,
+        (Outcome::Solved(answer), Some(expected)) if answer == expected => Verdict::Pass,
+        (Outcome::Solved(_), Some(_)) => Verdict::Mismatch,
+        (_, Some(_)) => Verdict::Error,
+    }
+}
+
+/// Describe the outcome of `report`, along with `expected` when it differs.
+pub fn details(report: &Report, expected: Option<&Answer>) -> String {
+    match (&report.outcome, expected) {
+        (Outcome::Solved(answer), Some(expected)) if answer != expected => {
+            format!("expected {expected}, got {answer}")
+        },
+        (Outcome::Solved(answer), _) => answer.to_string(),
+        (Outcome::Skipped(reason), _) => format!("skipped ({reason})"),
+        (Outcome::Invalid(error), _) => format!("failed ({error})"),
+        (Outcome::Failed(reason), _) => format!("failed ({reason})"),
+        (Outcome::TimedOut, _) => format!("timed out after {:.2?}", report.elapsed),
+    }
+}
+
+/// Solve both parts of `days` on up to `jobs` threads, reading their inputs
+/// from `inputs`, to be checked against `answers`. Without any day, those of
+/// `answers` are solved. The cached answers are never used, as they could
+/// hide a change to a solution that gives another answer.
+pub fn solve(
+    calendar: &Calendar,
+    days: &[u8],
+    answers: &Answers,
+    inputs: &Path,
+    jobs: usize,
+    timeout: Option<Duration>,
+) -> Result<Vec<Report>, String> {
+    let days = if days.is_empty() { answers.days() } else { days.to_vec() };
+    let puzzles = days
+        .into_iter()
+        .map(|day| {
+            calendar
+                .get(day)
+                .ok_or_else(|| format!("day{day} is not part of {}", calendar.year))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let tasks = Task::days(&puzzles, inputs);
+    Ok(runner::run_tasks(&tasks, jobs, timeout))
+}
+
+/// Compare every report against `answers`, print the results and return
+/// whether none of them failed.
+pub fn print_verdicts(reports: &[Report], answers: &Answers) -> bool {
+    let mut passed = true;
+
+    println!("{:>4} {:>4}  {:<8} Details", "Day", "Part", "Status");
+    for report in reports {
+        let expected = answers.get(report.day, report.part.number());
+        let verdict = check(report, expected);
+        passed &= !verdict.is_failure();
+
+        println!(
+            "{:>4} {:>4}  {:<8} {}",
+            report.day,
+            report.part.number(),
+            verdict.status(),
+            details(report, expected)
+        );
+    }
+
+    passed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::cache::{self, Cache};
+    use crate::calendar;
+    use crate::fixture::Scratch;
+    use crate::input::Input;
+    use crate::solution::Part;
+
+    fn report(outcome: Outcome) -> Report {
+        Report {
+            outcome,
+            elapsed: Duration::from_millis(3),
+            ..Report::skipped(5, Part::Part2, "")
+        }
+    }
+
+    #[test]
+    fn answers_are_read_from_toml() {
+        let answers = Answers::parse(
+            "[[answer]]\nday = 5\npart = 2\nexpected = 46\n\n[[answer]]\nday = 1\npart = \
+             1\nexpected = \"abc\"\n",
+            calendar::latest(),
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(5, 2), Some(&46u32.into()));
+        assert_eq!(answers.get(1, 1), Some(&"abc".into()));
+        assert_eq!(answers.get(5, 1), None);
+        assert_eq!(answers.days(), [1, 5]);
+        assert!(Answers::parse("", calendar::latest())
+            .unwrap()
+            .answers
+            .is_empty());
+    }
+
+    #[test]
+    fn answers_of_unknown_days_and_parts_are_rejected() {
+        let error = Answers::parse(
+            "[[answer]]\nday = 30\npart = 1\nexpected = 1\n",
+            calendar::latest(),
+        );
+        assert_eq!(
+            error.err().unwrap(),
+            "answer for day 30 is not in day1..=day25 of 2023"
+        );
+        let error = Answers::parse(
+            "[[answer]]\nday = 3\npart = 0\nexpected = 1\n",
+            calendar::latest(),
+        );
+        assert_eq!(
+            error.err().unwrap(),
+            "answer for part 0 of day 3 is not part1 or part2"
+        );
+        assert!(Answers::parse("[[answer]]\nday = 3\n", calendar::latest()).is_err());
+    }
+
+    #[test]
+    fn reports_are_checked_against_the_expected_answer() {
+        let expected: Answer = 46u32.into();
+        let solved = report(Outcome::Solved(46u32.into()));
+        let wrong = report(Outcome::Solved(45u32.into()));
+        let failed = report(Outcome::Failed("oops".into()));
+        let skipped = report(Outcome::Skipped("unimplemented".into()));
+        let timed_out = report(Outcome::TimedOut);
+
+        assert!(matches!(check(&solved, Some(&expected)), Verdict::Pass));
+        assert!(matches!(check(&wrong, Some(&expected)), Verdict::Mismatch));
+        assert!(matches!(check(&failed, Some(&expected)), Verdict::Error));
+        assert!(matches!(check(&skipped, Some(&expected)), Verdict::Error));
+        assert!(matches!(check(&timed_out, Some(&expected)), Verdict::Error));
+        assert!(matches!(check(&failed, None), Verdict::Unknown));
+
+        assert_eq!(details(&solved, Some(&expected)), "46");
+        assert_eq!(details(&wrong, Some(&expected)), "expected 46, got 45");
+        assert_eq!(details(&wrong, None), "45");
+        assert_eq!(details(&failed, None), "failed (oops)");
+        assert_eq!(details(&skipped, None), "skipped (unimplemented)");
+        assert_eq!(details(&timed_out, None), "timed out after 3.00ms");
+    }
+
+    #[test]
+    fn cached_answers_are_solved_again() {
+        let dir = Scratch::new("verify-cache");
+        let text = "1abc2\n";
+        fs::write(dir.join("day1.txt"), text).unwrap();
+        let answers = Answers::parse(
+            "[[answer]]\nday = 1\npart = 1\nexpected = 12\n",
+            calendar::latest(),
+        )
+        .unwrap();
+
+        // A wrong answer cached for this very input, as left by an older
+        // version of the solution.
+        let saved = fs::read(cache::PATH).ok();
+        let mut cache = Cache::load_for(cache::PATH, cache::VERSION).unwrap();
+        let hash = cache::hash(&Input::from(text));
+        cache.insert(calendar::latest().year, 1, 1, hash, 13u32.into());
+        cache.save().unwrap();
+
+        let reports = solve(calendar::latest(), &[1], &answers, &dir, 1, None);
+
+        match saved {
+            Some(saved) => fs::write(cache::PATH, saved).unwrap(),
+            None => fs::remove_file(cache::PATH).unwrap(),
+        }
+        let reports = reports.unwrap();
+        assert_eq!(reports[0].outcome, Outcome::Solved(12u32.into()));
+        assert!(!reports[0].cached);
+        assert!(matches!(
+            check(&reports[0], answers.get(1, 1)),
+            Verdict::Pass
+        ));
+    }
+}
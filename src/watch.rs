@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{fs, io, thread};
+
+use crate::input::Input;
+use crate::runner::{self, Report};
+use crate::solution::{Part, Puzzle};
+use crate::verify;
+
+/// Delay between two checks of the watched files.
+const POLL: Duration = Duration::from_millis(500);
+
+/// Polls an input and its sibling examples for changes.
+pub struct Watcher {
+    input: PathBuf,
+    day: u8,
+    stamps: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    pub fn new(input: &Path, day: u8) -> Self {
+        let mut watcher = Watcher {
+            input: input.to_path_buf(),
+            day,
+            stamps: Vec::new(),
+        };
+        watcher.stamps = watcher.scan();
+        watcher
+    }
+
+    /// Watched files, the input first and then its siblings.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.stamps.iter().map(|(p, _)| p.as_path())
+    }
+
+    /// Block until a watched file is modified, created or removed.
+    pub fn wait(&mut self) {
+        loop {
+            thread::sleep(POLL);
+            if self.changed() {
+                return;
+            }
+        }
+    }
+
+    /// Whether a watched file was modified, created or removed since the
+    /// last check.
+    fn changed(&mut self) -> bool {
+        let stamps = self.scan();
+        if stamps == self.stamps {
+            return false;
+        }
+        self.stamps = stamps;
+        true
+    }
+
+    fn scan(&self) -> Vec<(PathBuf, Option<SystemTime>)> {
+        let mut files = vec![self.input.clone()];
+        files.extend(siblings(&self.input, self.day).unwrap_or_default());
+        files
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, modified)
+            })
+            .collect()
+    }
+}
+
+/// Solve `parts` on `input` and its siblings, then again whenever one of them
+/// changes, printing what changed since the previous run.
+pub fn run(puzzle: &dyn Puzzle, parts: &[Part], input: &Path) -> ! {
+    let mut watcher = Watcher::new(input, puzzle.metadata().day);
+    let mut previous = Vec::new();
+    for run in 1.. {
+        println!("run #{run}");
+        let mut reports = Vec::new();
+        for file in watcher.files() {
+            println!("{}", file.display());
+            match Input::read(file) {
+                Ok(input) => {
+                    let current = runner::run(puzzle, parts, &input);
+                    print_changes(&current, &previous);
+                    reports.extend(current);
+                },
+                Err(e) => println!("  could not read: {e}"),
+            }
+        }
+        println!();
+
+        previous = reports;
+        watcher.wait();
+    }
+    unreachable!("runs are numbered forever")
+}
+
+/// Files next to `input` that belong to `day`, such as `day12-example.txt`
+/// beside `day12.txt`.
+fn siblings(input: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+    let directory = match input.parent() {
+        Some(parent) if parent != Path::new("") => parent,
+        _ => Path::new("."),
+    };
+    let prefix = format!("day{day}");
+
+    let mut siblings = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let belongs = name
+            .strip_prefix(&prefix)
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit()));
+        if belongs && path.is_file() && path.file_name() != input.file_name() {
+            siblings.push(path);
+        }
+    }
+    siblings.sort();
+
+    Ok(siblings)
+}
+
+/// Print `reports`, along with what changed since the matching report of
+/// `previous`.
+pub fn print_changes(reports: &[Report], previous: &[Report]) {
+    for report in reports {
+        let before = previous
+            .iter()
+            .find(|r| r.part == report.part && r.input == report.input);
+
+        let mut answer = verify::details(report, None);
+        let mut elapsed = format!("{:.2?}", report.elapsed);
+        if let Some(before) = before {
+            if before.outcome != report.outcome {
+                answer += &format!(" (was {})", verify::details(before, None));
+            }
+            if !before.elapsed.is_zero() && !report.elapsed.is_zero() {
+                let change = report.elapsed.as_secs_f64() / before.elapsed.as_secs_f64() - 1.0;
+                elapsed += &format!(" ({:+.1}%)", change * 100.0);
+            }
+        }
+
+        println!("  part{}  {:<40} {}", report.part.number(), answer, elapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use super::*;
+    use crate::fixture::Scratch;
+
+    #[test]
+    fn siblings_belong_to_the_same_day() {
+        let dir = Scratch::new("siblings");
+        fs::create_dir(dir.join("day1c.txt")).unwrap();
+        for name in [
+            "day1.txt",
+            "day1b.txt",
+            "day10.txt",
+            "day12.txt",
+            "day12-example.txt",
+            "notes.txt",
+        ] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        assert_eq!(
+            siblings(&dir.join("day1.txt"), 1).unwrap(),
+            [dir.join("day1b.txt")]
+        );
+        assert_eq!(
+            siblings(&dir.join("day12.txt"), 12).unwrap(),
+            [dir.join("day12-example.txt")]
+        );
+        assert!(siblings(&dir.join("day3.txt"), 3).unwrap().is_empty());
+        // A bare file name is looked up in the working directory.
+        assert!(siblings(Path::new("day1.txt"), 1).is_ok());
+    }
+
+    #[test]
+    fn changes_of_the_watched_files_are_seen() {
+        let dir = Scratch::new("watcher");
+        let input = dir.join("day2.txt");
+        let example = dir.join("day2-example.txt");
+        fs::write(&input, "").unwrap();
+
+        let mut watcher = Watcher::new(&input, 2);
+        assert_eq!(watcher.files().collect::<Vec<_>>(), [input.as_path()]);
+        assert!(!watcher.changed());
+
+        fs::write(dir.join("day20.txt"), "").unwrap();
+        assert!(!watcher.changed());
+
+        fs::write(&example, "").unwrap();
+        assert!(watcher.changed());
+        assert_eq!(
+            watcher.files().collect::<Vec<_>>(),
+            [input.as_path(), example.as_path()]
+        );
+        assert!(!watcher.changed());
+
+        let modified = SystemTime::now() + Duration::from_secs(60);
+        File::options()
+            .write(true)
+            .open(&input)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert!(watcher.changed());
+
+        fs::remove_file(&input).unwrap();
+        watcher.wait();
+        assert!(!watcher.changed());
+    }
+}
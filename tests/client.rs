@@ -0,0 +1,331 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::{fs, thread};
+
+use advent::client::{Client, Config, Error, Fetched, Submission, Throttle, Verdict};
+use advent::fixture::Scratch;
+use advent::history::{History, Refusal};
+use advent::{Answer, Part};
+
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+struct Response {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: &str) -> Self {
+        Response {
+            status: 200,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+}
+
+/// Local HTTP server answering every request with `handler`.
+struct Stub {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl Stub {
+    fn start(handler: impl Fn(&Request) -> Response + Send + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let log = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut words = line.split_whitespace();
+                let method = words.next().unwrap_or_default().to_string();
+                let path = words.next().unwrap_or_default().to_string();
+
+                let mut headers = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let Some((key, value)) = line.trim_end().split_once(':') else {
+                        break;
+                    };
+                    headers.push((key.to_string(), value.trim().to_string()));
+                }
+
+                let mut request = Request {
+                    method,
+                    path,
+                    headers,
+                    body: String::new(),
+                };
+                let length = request
+                    .header("Content-Length")
+                    .map_or(0, |n| n.parse().unwrap());
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                request.body = String::from_utf8(body).unwrap();
+
+                let response = handler(&request);
+                log.lock().unwrap().push(request);
+
+                let mut head = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n",
+                    response.status,
+                    response.body.len()
+                );
+                for (key, value) in response.headers {
+                    head.push_str(&format!("{key}: {value}\r\n"));
+                }
+                write!(stream, "{head}\r\n{}", response.body).unwrap();
+            }
+        });
+
+        Stub { url, requests }
+    }
+
+    fn requests(&self) -> usize { self.requests.lock().unwrap().len() }
+}
+
+fn client(stub: &Stub, dir: &Path, interval: Duration) -> Client {
+    Client::new(
+        &stub.url,
+        "secret",
+        Throttle::new(interval, dir.join("last-request")),
+    )
+}
+
+#[test]
+fn fetch_downloads_the_input() {
+    let dir = Scratch::new("download");
+    let stub = Stub::start(|_| Response::ok("1abc2\npqr3stu8vwx\n"));
+    let path = dir.join("inputs/2023/day1.txt");
+
+    let fetched = client(&stub, &dir, Duration::ZERO).fetch(2023, 1, &path);
+
+    assert_eq!(fetched.unwrap(), Fetched::Downloaded);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1abc2\npqr3stu8vwx\n");
+    let requests = stub.requests.lock().unwrap();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2023/day/1/input");
+    assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+}
+
+#[test]
+fn fetch_never_refetches_an_existing_input() {
+    let dir = Scratch::new("existing");
+    let stub = Stub::start(|_| Response::ok("new"));
+    let path = dir.join("day1.txt");
+    fs::write(&path, "old").unwrap();
+
+    let fetched = client(&stub, &dir, Duration::ZERO).fetch(2023, 1, &path);
+
+    assert_eq!(fetched.unwrap(), Fetched::Existing);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+    assert_eq!(stub.requests(), 0);
+}
+
+#[test]
+fn fetch_keeps_nothing_on_error() {
+    let dir = Scratch::new("error");
+    let stub = Stub::start(|_| Response {
+        status: 400,
+        headers: Vec::new(),
+        body: "Please log in to get your puzzle input.".to_string(),
+    });
+    let path = dir.join("day1.txt");
+
+    let fetched = client(&stub, &dir, Duration::ZERO).fetch(2023, 1, &path);
+
+    assert!(matches!(fetched, Err(Error::Status(400, _))), "{fetched:?}");
+    assert!(!path.exists());
+    assert!(!dir.join("day1.part").exists());
+}
+
+#[test]
+fn fetch_reports_rate_limits() {
+    let dir = Scratch::new("rate-limit");
+    let stub = Stub::start(|_| Response {
+        status: 429,
+        headers: vec![("Retry-After", "60".to_string())],
+        body: String::new(),
+    });
+
+    let fetched = client(&stub, &dir, Duration::ZERO).fetch(2023, 1, &dir.join("day1.txt"));
+
+    assert!(
+        matches!(fetched, Err(Error::RateLimited(Some(wait))) if wait == Duration::from_secs(60)),
+        "{fetched:?}"
+    );
+}
+
+#[test]
+fn fetch_spaces_out_requests() {
+    let dir = Scratch::new("throttle");
+    let stub = Stub::start(|_| Response::ok("input"));
+    let interval = Duration::from_millis(300);
+
+    let start = Instant::now();
+    client(&stub, &dir, interval)
+        .fetch(2023, 1, &dir.join("day1.txt"))
+        .unwrap();
+    // A new client shares the throttle through its state file.
+    client(&stub, &dir, interval)
+        .fetch(2023, 2, &dir.join("day2.txt"))
+        .unwrap();
+
+    assert!(start.elapsed() >= interval);
+    assert_eq!(stub.requests(), 2);
+}
+
+#[test]
+fn config_reads_every_setting() {
+    let dir = Scratch::new("config");
+    let path = dir.join("advent.toml");
+    fs::write(
+        &path,
+        "session = \"secret\"\nbase_url = \"http://localhost\"\ninterval = 1.5\n",
+    )
+    .unwrap();
+
+    let config = Config::load(&path).unwrap();
+
+    assert_eq!(config.session.as_deref(), Some("secret"));
+    assert_eq!(config.base_url.as_deref(), Some("http://localhost"));
+    assert_eq!(config.interval, Some(1.5));
+    assert!(Config::load(&dir.join("missing.toml")).is_ok());
+}
+
+#[test]
+fn submit_posts_the_answer() {
+    let dir = Scratch::new("submit");
+    let stub = Stub::start(|_| {
+        Response::ok(
+            "<article><p>That's the right answer! You are one gold star closer.</p></article>",
+        )
+    });
+
+    let submission =
+        client(&stub, &dir, Duration::ZERO).submit(2023, 1, Part::Part2, &Answer::from(281u32));
+
+    assert_eq!(
+        submission.unwrap(),
+        Submission {
+            verdict: Verdict::Correct,
+            wait: None
+        }
+    );
+    let requests = stub.requests.lock().unwrap();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2023/day/1/answer");
+    assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+    assert_eq!(requests[0].body, "level=2&answer=281");
+}
+
+#[test]
+fn submission_reads_the_verdict_and_cooldown() {
+    let wrong = Submission::read(
+        "That's not the right answer; your answer is too high. Because you have guessed \
+         incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.",
+    );
+    let first = Submission::read(
+        "That's not the right answer. If you're stuck, make sure you're using the full input \
+         data; please wait one minute before trying again.",
+    );
+    let recent = Submission::read(
+        "You gave an answer too recently; you have to wait after submitting an answer before \
+         trying again.  You have 1m 5s left to wait.",
+    );
+    let level = Submission::read(
+        "You don't seem to be solving the right level.  Did you already complete it?",
+    );
+
+    assert_eq!(wrong.verdict, Verdict::TooHigh);
+    assert_eq!(wrong.wait, Some(Duration::from_secs(300)));
+    assert_eq!(first.verdict, Verdict::Wrong);
+    assert_eq!(first.wait, Some(Duration::from_secs(60)));
+    assert_eq!(recent.verdict, Verdict::TooRecent);
+    assert_eq!(recent.wait, Some(Duration::from_secs(65)));
+    assert_eq!(level.verdict, Verdict::WrongLevel);
+    assert_eq!(level.wait, None);
+}
+
+#[test]
+fn history_refuses_known_answers() {
+    let dir = Scratch::new("history");
+    let path = dir.join("submissions.json");
+    let wrong = Submission {
+        verdict: Verdict::TooLow,
+        wait: None,
+    };
+    let correct = Submission {
+        verdict: Verdict::Correct,
+        wait: None,
+    };
+
+    let mut history = History::load(&path).unwrap();
+    history.record(1, 1, Answer::from(10u32), wrong);
+    history.save().unwrap();
+    let mut history = History::load(&path).unwrap();
+
+    assert_eq!(
+        history.check(1, 1, &Answer::from(10u32)),
+        Err(Refusal::Rejected(Verdict::TooLow))
+    );
+    assert_eq!(history.check(1, 1, &Answer::from(11u32)), Ok(()));
+    assert_eq!(history.check(1, 2, &Answer::from(10u32)), Ok(()));
+
+    history.record(1, 1, Answer::from(12u32), correct);
+    assert_eq!(
+        history.check(1, 1, &Answer::from(13u32)),
+        Err(Refusal::Solved(Answer::from(12u32)))
+    );
+}
+
+#[test]
+fn history_honours_the_cooldown() {
+    let dir = Scratch::new("cooldown");
+    let mut history = History::load(dir.join("submissions.json")).unwrap();
+    history.record(
+        1,
+        1,
+        Answer::from(10u32),
+        Submission {
+            verdict: Verdict::TooRecent,
+            wait: Some(Duration::from_secs(60)),
+        },
+    );
+
+    // The cooldown holds for every puzzle, and a late answer can be retried.
+    assert!(matches!(
+        history.check(2, 1, &Answer::from(5u32)),
+        Err(Refusal::Cooldown(wait)) if wait <= Duration::from_secs(60)
+    ));
+    assert!(matches!(
+        history.check(1, 1, &Answer::from(10u32)),
+        Err(Refusal::Cooldown(_))
+    ));
+}
@@ -0,0 +1,150 @@
+use advent::input::Input;
+use advent::runner::{self, Outcome};
+use advent::solution::Part;
+use advent::{days, example};
+
+fn check_examples(day: u8) {
+    let puzzle = days::get(day).unwrap();
+    let checks = example::check(puzzle);
+    assert!(!puzzle.examples().is_empty(), "day{day} has no examples");
+    assert!(
+        !checks.is_empty(),
+        "the examples of day{day} have no expected answers, fill in their `part1` and `part2`"
+    );
+
+    for check in checks {
+        let context = format!(
+            "day{day} example {} part {}",
+            check.example,
+            check.report.part.number()
+        );
+        match &check.report.outcome {
+            Outcome::Solved(answer) => assert_eq!(answer, check.expected, "{context}"),
+            Outcome::Skipped(reason) => panic!("{context} skipped: {reason}"),
+            Outcome::Invalid(error) => panic!("{context} does not parse: {error}"),
+            Outcome::Failed(reason) => panic!("{context} failed: {reason}"),
+            Outcome::TimedOut => panic!("{context} timed out"),
+        }
+    }
+}
+
+macro_rules! examples {
+    ($($(#[$attribute:meta])* $name:ident => $day:literal),* $(,)?) => {
+        $(
+            #[test]
+            $(#[$attribute])*
+            fn $name() { check_examples($day); }
+        )*
+    };
+}
+
+examples! {
+    day1 => 1,
+    day2 => 2,
+    day3 => 3,
+    day4 => 4,
+    day5 => 5,
+    day6 => 6,
+    day7 => 7,
+    day8 => 8,
+    day9 => 9,
+    day10 => 10,
+    day11 => 11,
+    day12 => 12,
+    day13 => 13,
+    day14 => 14,
+    day15 => 15,
+    day16 => 16,
+    day17 => 17,
+    day18 => 18,
+    day19 => 19,
+}
+
+/// Outcomes of both parts of `day` on `input`.
+fn solve(day: u8, input: &str) -> Vec<Outcome> {
+    let puzzle = days::get(day).unwrap();
+    runner::run(puzzle, &Part::ALL, &Input::from(input))
+        .into_iter()
+        .map(|report| report.outcome)
+        .collect()
+}
+
+#[test]
+fn overflows_fail_the_part_they_happen_in() {
+    let race = "Time: 4294967295 2\nDistance: 1 0\n";
+    let outcomes = solve(6, race);
+    assert!(matches!(outcomes[0], Outcome::Solved(_)), "{outcomes:?}");
+    assert!(matches!(outcomes[1], Outcome::Failed(_)), "{outcomes:?}");
+
+    let lagoon = "R 9223372036854775807 (#000010)\nR 1 (#000010)\n";
+    let outcomes = solve(18, lagoon);
+    assert!(matches!(outcomes[0], Outcome::Failed(_)), "{outcomes:?}");
+    assert!(matches!(outcomes[1], Outcome::Solved(_)), "{outcomes:?}");
+}
+
+#[test]
+fn day8_fails_when_the_ghosts_never_meet_at_an_end() {
+    let undefined = "L\n\nAAA = (ZZZ, ZZZ)\n";
+    assert!(matches!(solve(8, undefined)[0], Outcome::Invalid(_)));
+
+    let apart = "L\n\nAAA = (AAZ, AAZ)\nAAZ = (AAA, AAA)\nBBA = (BBB, BBB)\nBBB = (BBZ, BBZ)\nBBZ \
+                 = (BBB, BBB)\n";
+    let outcomes = solve(8, apart);
+    assert_eq!(
+        outcomes[1],
+        Outcome::Failed("the ghosts are never all at an end at once".to_string())
+    );
+
+    let lonely = "L\n\nZZZ = (ZZZ, ZZZ)\n";
+    assert_eq!(
+        solve(8, lonely),
+        [
+            Outcome::Failed("there is no node `AAA`".to_string()),
+            Outcome::Failed("there is no node ending with `A`".to_string())
+        ]
+    );
+}
+
+#[test]
+fn day6_rows_have_as_many_numbers() {
+    let outcomes = solve(6, "Time: 7 15\nDistance: 9\n");
+    match &outcomes[0] {
+        Outcome::Invalid(error) => assert_eq!((error.line, error.column), (2, 12)),
+        outcome => panic!("expected a parse error, got {outcome:?}"),
+    }
+}
+
+#[test]
+fn day9_fails_on_histories_that_overflow() {
+    let outcomes = solve(9, "9223372036854775807 -9223372036854775808\n");
+    assert!(
+        outcomes.iter().all(|o| matches!(o, Outcome::Failed(_))),
+        "{outcomes:?}"
+    );
+}
+
+#[test]
+fn day16_fails_on_an_empty_contraption() {
+    let outcomes = solve(16, "");
+    assert!(
+        outcomes.iter().all(|o| matches!(o, Outcome::Failed(_))),
+        "{outcomes:?}"
+    );
+}
+
+#[test]
+fn day17_fails_when_the_end_is_out_of_reach() {
+    let column = "1\n1\n1\n1\n1\n";
+    assert_eq!(
+        solve(17, column),
+        [
+            Outcome::Failed("the crucible cannot reach the end".to_string()),
+            Outcome::Solved(4u32.into())
+        ]
+    );
+    assert_eq!(
+        solve(17, "7\n"),
+        [Outcome::Solved(0u32.into()), Outcome::Solved(0u32.into())]
+    );
+    assert!(matches!(solve(17, "")[0], Outcome::Failed(_)));
+}
@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+
+use advent::days::day12::{search_matches, Spring};
+use advent::days::day18::dig;
+use advent::days::day5::Range;
+use advent::days::day6::{ways, Race};
+use advent::geometry::{Direction, Point};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn day6_ways_counts_winning_holds(time in 0..200u64, distance in 0..11_000u64) {
+        let expected = (0..=time).filter(|hold| hold * (time - hold) > distance).count();
+        prop_assert_eq!(ways(Race { time, distance }), Some(expected as u64));
+    }
+
+    #[test]
+    fn day5_range_match_maps_every_value(
+        src in 0..40u64,
+        dst in 0..40u64,
+        range in 1..20u64,
+        seed in 0..60u64,
+        seeds in 1..30u64,
+    ) {
+        let map = Range { src, dst, range };
+        let (matched, not_matched) = map.range_match((seed, seeds));
+
+        let values = |ranges: &[(u64, u64)]| {
+            prop_assert!(ranges.iter().all(|&(_, length)| length > 0), "{ranges:?}");
+            let mut values: Vec<u64> = ranges
+                .iter()
+                .flat_map(|&(start, length)| start..start + length)
+                .collect();
+            values.sort();
+            Ok(values)
+        };
+        let mut expected_matched: Vec<u64> =
+            (seed..seed + seeds).filter_map(|x| map.src_to_dst(x)).collect();
+        expected_matched.sort();
+        let expected_not_matched: Vec<u64> = (seed..seed + seeds)
+            .filter(|&x| map.src_to_dst(x).is_none())
+            .collect();
+
+        prop_assert_eq!(values(&matched)?, expected_matched);
+        prop_assert_eq!(values(&not_matched)?, expected_not_matched);
+    }
+
+    #[test]
+    fn day12_search_matches_counts_arrangements(
+        row in prop::collection::vec(
+            prop::sample::select(vec![Spring::Operational, Spring::Damaged, Spring::Unknown]),
+            1..12,
+        ),
+        counts in prop::collection::vec(1..4usize, 0..4),
+    ) {
+        prop_assert_eq!(search_matches((row.clone(), counts.clone())), arrangements(&row, &counts));
+    }
+
+    #[test]
+    fn day18_dig_counts_dug_cells(
+        blocks in prop::collection::vec((1..5i64, -6..=0i64, 1..=6i64), 1..6),
+    ) {
+        let instructions = outline(&blocks);
+        prop_assert_eq!(dig(instructions.clone()), Ok(flood(&instructions)));
+    }
+}
+
+/// Number of ways to replace the unknown springs of `row` so that its groups
+/// of damaged springs have the sizes in `counts`.
+fn arrangements(row: &[Spring], counts: &[usize]) -> usize {
+    let unknown: Vec<usize> = (0..row.len())
+        .filter(|&n| row[n] == Spring::Unknown)
+        .collect();
+
+    (0..1u32 << unknown.len())
+        .filter(|mask| {
+            let mut row = row.to_vec();
+            for (bit, &n) in unknown.iter().enumerate() {
+                row[n] = if mask >> bit & 1 == 1 { Spring::Damaged } else { Spring::Operational };
+            }
+            let groups: Vec<usize> = row
+                .split(|&s| s == Spring::Operational)
+                .map(<[Spring]>::len)
+                .filter(|&len| len > 0)
+                .collect();
+            groups == counts
+        })
+        .count()
+}
+
+/// Trench around columns of `(width, bottom, top)` blocks laid side by side,
+/// which all cover rows 0 and 1 so that the outline never touches itself.
+fn outline(blocks: &[(i64, i64, i64)]) -> Vec<(Direction, i64)> {
+    let vertical = |from: i64, to: i64| {
+        if to >= from {
+            (Direction::Down, to - from)
+        } else {
+            (Direction::Up, from - to)
+        }
+    };
+
+    let mut instructions = Vec::new();
+    for (n, &(width, bottom, _)) in blocks.iter().enumerate() {
+        instructions.push((Direction::Right, width));
+        if let Some(&(_, next, _)) = blocks.get(n + 1) {
+            instructions.push(vertical(bottom, next));
+        }
+    }
+    let &(_, bottom, top) = blocks.last().unwrap();
+    instructions.push(vertical(bottom, top));
+    for (n, &(width, _, top)) in blocks.iter().enumerate().rev() {
+        instructions.push((Direction::Left, width));
+        if let Some(&(_, _, previous)) = n.checked_sub(1).map(|n| &blocks[n]) {
+            instructions.push(vertical(top, previous));
+        }
+    }
+    let &(_, bottom, top) = blocks.first().unwrap();
+    instructions.push(vertical(top, bottom));
+    instructions
+}
+
+/// Cells of the trench and of the area it encloses, found by filling the
+/// outside of the trench one cell at a time.
+fn flood(instructions: &[(Direction, i64)]) -> usize {
+    let mut trench = HashSet::from([Point::default()]);
+    let mut position = Point::default();
+    for &(direction, distance) in instructions {
+        for _ in 0..distance {
+            position = position.step(direction, 1).unwrap();
+            trench.insert(position);
+        }
+    }
+    assert_eq!(position, Point::default(), "the trench is not a loop");
+
+    // Bounding box of the trench with a margin, so that the outside is
+    // connected.
+    let rows = trench.iter().map(|p| p.row);
+    let columns = trench.iter().map(|p| p.column);
+    let (top, bottom) = (rows.clone().min().unwrap() - 1, rows.max().unwrap() + 1);
+    let (left, right) = (
+        columns.clone().min().unwrap() - 1,
+        columns.max().unwrap() + 1,
+    );
+    let inside = |p: &Point| (top..=bottom).contains(&p.row) && (left..=right).contains(&p.column);
+
+    let start = Point::new(top, left);
+    let mut outside = HashSet::from([start]);
+    let mut queue = vec![start];
+    while let Some(point) = queue.pop() {
+        for direction in Direction::ALL {
+            let next = point.step(direction, 1).unwrap();
+            if inside(&next) && !trench.contains(&next) && outside.insert(next) {
+                queue.push(next);
+            }
+        }
+    }
+
+    let total = (bottom - top + 1) * (right - left + 1);
+    total as usize - outside.len()
+}